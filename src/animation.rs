@@ -1,9 +1,9 @@
 extern crate serde_json;
 use serde_json::json;
-use terminal;
 
-use color_glyph::{ColorGlyph, EMPTY_COLOR_GLYPH};
-use error::error;
+use color_glyph::{match_color, ColorGlyph, EMPTY_COLOR_GLYPH};
+use error::ByofishError;
+use width::is_single_width;
 
 #[derive(Clone, Copy, Eq, PartialEq)]
 pub struct Position {
@@ -16,17 +16,20 @@ pub struct Size {
     pub width: usize,
 }
 
-pub type Animation = Vec<Vec<Vec<ColorGlyph>>>;
+pub type Frame = Vec<Vec<ColorGlyph>>;
+pub type Animation = Vec<Frame>;
+// how long each frame is shown in milliseconds, None when the file doesn't say
+pub type Durations = Vec<Option<u64>>;
 
 pub fn blank_animation(size: Size) -> Animation {
-    return vec![vec![vec![EMPTY_COLOR_GLYPH; size.width as usize]; (size.height) as usize]; 1];
+    return vec![vec![vec![EMPTY_COLOR_GLYPH; size.width]; size.height]; 1];
 }
 
-pub fn load_animation(json: &serde_json::Value, name: &str, anim_key: &str) -> Animation {
+pub fn load_animation(json: &serde_json::Value, anim_key: &str) -> Result<Animation, ByofishError> {
     let mut out_anim: Animation = Vec::new();
 
     if json.pointer(anim_key).is_none() {
-        error(&format!("{} {} key is missing", name, anim_key), 1);
+        return Err(ByofishError::MissingKey(String::from(anim_key)));
     }
 
     let symbols = json
@@ -39,29 +42,18 @@ pub fn load_animation(json: &serde_json::Value, name: &str, anim_key: &str) -> A
         .pointer(&format!("{}/highlights", anim_key))
         .unwrap_or(&json!(null));
 
-    check_format(&symbols, &format!("{} {}/symbols", name, anim_key));
-    check_format(&colors, &format!("{} {}/colors", name, anim_key));
-    check_format(&highlights, &format!("{} {}highlights", name, anim_key));
+    check_format(symbols, &format!("{}/symbols", anim_key))?;
+    check_format(colors, &format!("{}/colors", anim_key))?;
+    check_format(highlights, &format!("{}/highlights", anim_key))?;
 
     let num_frames = symbols.as_array().unwrap().len();
     let num_lines = symbols[0].as_array().unwrap().len();
-    let num_symbols = symbols[0][0].as_str().unwrap().len();
-
-    check_array(
-        &symbols,
-        num_frames,
-        &format!("{} {}/symbols", name, anim_key),
-    );
-    check_array(
-        &colors,
-        num_frames,
-        &format!("{} {}/colors", name, anim_key),
-    );
-    check_array(
-        &highlights,
-        num_frames,
-        &format!("{} {}/highlights", name, anim_key),
-    );
+    // counted in chars since glyphs like ° or ─ take more than one byte
+    let num_symbols = symbols[0][0].as_str().unwrap().chars().count();
+
+    check_array(symbols, num_frames, &format!("{}/symbols", anim_key))?;
+    check_array(colors, num_frames, &format!("{}/colors", anim_key))?;
+    check_array(highlights, num_frames, &format!("{}/highlights", anim_key))?;
 
     for frame_idx in 0..num_frames {
         let mut out_frame: Vec<Vec<ColorGlyph>> = Vec::new();
@@ -69,18 +61,18 @@ pub fn load_animation(json: &serde_json::Value, name: &str, anim_key: &str) -> A
         check_array(
             &symbols[frame_idx],
             num_lines,
-            &format!("{} {}/symbols[{}]", name, anim_key, frame_idx),
-        );
+            &format!("{}/symbols/{}", anim_key, frame_idx),
+        )?;
         check_array(
             &colors[frame_idx],
             num_lines,
-            &format!("{} {}/colors[{}]", name, anim_key, frame_idx),
-        );
+            &format!("{}/colors/{}", anim_key, frame_idx),
+        )?;
         check_array(
             &highlights[frame_idx],
             num_lines,
-            &format!("{} {}/highlights[{}]", name, anim_key, frame_idx),
-        );
+            &format!("{}/highlights/{}", anim_key, frame_idx),
+        )?;
 
         for line_idx in 0..num_lines {
             let mut out_line: Vec<ColorGlyph> = Vec::new();
@@ -88,116 +80,145 @@ pub fn load_animation(json: &serde_json::Value, name: &str, anim_key: &str) -> A
             check_string(
                 &symbols[frame_idx][line_idx],
                 num_symbols,
-                &format!("{} {}/symbols[{}][{}]", name, anim_key, frame_idx, line_idx),
-            );
+                &format!("{}/symbols/{}/{}", anim_key, frame_idx, line_idx),
+            )?;
             check_string(
                 &colors[frame_idx][line_idx],
                 num_symbols,
-                &format!("{} {}/colors[{}][{}]", name, anim_key, frame_idx, line_idx),
-            );
+                &format!("{}/colors/{}/{}", anim_key, frame_idx, line_idx),
+            )?;
             check_string(
                 &highlights[frame_idx][line_idx],
                 num_symbols,
-                &format!(
-                    "{} {}/highlights[{}][{}]",
-                    name, anim_key, frame_idx, line_idx
-                ),
-            );
+                &format!("{}/highlights/{}/{}", anim_key, frame_idx, line_idx),
+            )?;
 
-            let line = symbols[frame_idx][line_idx].as_str().unwrap();
+            let line: Vec<char> = symbols[frame_idx][line_idx]
+                .as_str()
+                .unwrap()
+                .chars()
+                .collect();
+            let line_colors: Vec<char> = colors[frame_idx][line_idx]
+                .as_str()
+                .unwrap()
+                .chars()
+                .collect();
+            let line_highlights: Vec<char> = highlights[frame_idx][line_idx]
+                .as_str()
+                .unwrap()
+                .chars()
+                .collect();
 
             for symbol_idx in 0..num_symbols {
+                if !is_single_width(line[symbol_idx]) {
+                    return Err(ByofishError::WideGlyph(
+                        format!("{}/symbols/{}/{}", anim_key, frame_idx, line_idx),
+                        line[symbol_idx],
+                    ));
+                }
                 out_line.push(ColorGlyph {
-                    glyph: line.chars().nth(symbol_idx).unwrap(),
-                    foreground_color: match_color(
-                        colors.as_array().unwrap()[frame_idx].as_array().unwrap()[line_idx]
-                            .as_str()
-                            .unwrap()
-                            .chars()
-                            .nth(symbol_idx)
-                            .unwrap(),
-                    ),
-                    background_color: match_color(
-                        highlights.as_array().unwrap()[frame_idx]
-                            .as_array()
-                            .unwrap()[line_idx]
-                            .as_str()
-                            .unwrap()
-                            .chars()
-                            .nth(symbol_idx)
-                            .unwrap(),
-                    ),
+                    glyph: line[symbol_idx],
+                    foreground_color: match_color(line_colors[symbol_idx]),
+                    background_color: match_color(line_highlights[symbol_idx]),
                 });
             }
             out_frame.push(out_line);
         }
         out_anim.push(out_frame);
     }
-    return out_anim;
+    return Ok(out_anim);
+}
+
+// the durations are optional so files made before them still load
+pub fn load_durations(
+    json: &serde_json::Value,
+    anim_key: &str,
+    num_frames: usize,
+) -> Result<Durations, ByofishError> {
+    let pointer = format!("{}/durations", anim_key);
+    let durations = match json.pointer(&pointer) {
+        Some(durations) => durations,
+        None => return Ok(vec![None; num_frames]),
+    };
+    check_array(durations, num_frames, &pointer)?;
+
+    let mut out_durations: Durations = Vec::new();
+    for (frame_idx, duration) in durations.as_array().unwrap().iter().enumerate() {
+        if duration.is_null() {
+            out_durations.push(None);
+            continue;
+        }
+        match duration.as_u64() {
+            Some(ms) if ms > 0 => out_durations.push(Some(ms)),
+            _ => {
+                return Err(ByofishError::WrongType(
+                    format!("{}/{}", pointer, frame_idx),
+                    "a positive number of milliseconds",
+                ));
+            }
+        }
+    }
+    return Ok(out_durations);
 }
 
-fn check_format(json_array: &serde_json::Value, name: &str) {
+fn check_format(json_array: &serde_json::Value, pointer: &str) -> Result<(), ByofishError> {
     if json_array.is_null() {
-        error(&format!("{} key is missing", name), 1);
+        return Err(ByofishError::MissingKey(String::from(pointer)));
     }
     if !json_array.is_array() {
-        error(&format!("{} is not an array", name), 1);
+        return Err(ByofishError::WrongType(String::from(pointer), "an array"));
     }
     if !json_array[0].is_array() {
-        error(&format!("{}[0] is not an array", name), 1);
+        return Err(ByofishError::WrongType(
+            format!("{}/0", pointer),
+            "an array",
+        ));
     }
     if !json_array[0][0].is_string() {
-        error(&format!("{}[0][0] is not a string", name), 1);
+        return Err(ByofishError::WrongType(
+            format!("{}/0/0", pointer),
+            "a string",
+        ));
     }
-    if !json_array[0][0].as_str().unwrap().len() == 0 {
-        error(&format!("{}[0][0] is an empty string", name), 1);
+    if json_array[0][0].as_str().unwrap().is_empty() {
+        return Err(ByofishError::WrongType(
+            format!("{}/0/0", pointer),
+            "a non-empty string",
+        ));
     }
+    return Ok(());
 }
 
-fn check_array(json_array: &serde_json::Value, target_size: usize, name: &str) {
+fn check_array(
+    json_array: &serde_json::Value,
+    target_size: usize,
+    pointer: &str,
+) -> Result<(), ByofishError> {
     if !json_array.is_array() {
-        error(&format!("{} is not an array", name), 1);
+        return Err(ByofishError::WrongType(String::from(pointer), "an array"));
     }
     if json_array.as_array().unwrap().len() != target_size {
-        error(&format!("{} differs in length", name), 1);
+        return Err(ByofishError::LengthMismatch(String::from(pointer)));
     }
+    return Ok(());
 }
 
-fn check_string(json_string: &serde_json::Value, target_size: usize, name: &str) {
+fn check_string(
+    json_string: &serde_json::Value,
+    target_size: usize,
+    pointer: &str,
+) -> Result<(), ByofishError> {
     if !json_string.is_string() {
-        error(&format!("{} is not a string", name), 1);
+        return Err(ByofishError::WrongType(String::from(pointer), "a string"));
     }
-    if json_string.as_str().unwrap().len() != target_size {
-        error(&format!("{} differs in length", name), 1);
-    }
-}
-
-fn match_color(color: char) -> Option<terminal::Color> {
-    match color {
-        'a' => return Some(terminal::Color::DarkGrey),
-        'r' => return Some(terminal::Color::Red),
-        'g' => return Some(terminal::Color::Green),
-        'y' => return Some(terminal::Color::Yellow),
-        'b' => return Some(terminal::Color::Blue),
-        'm' => return Some(terminal::Color::Magenta),
-        'c' => return Some(terminal::Color::Cyan),
-        'w' => return Some(terminal::Color::White),
-
-        'A' => return Some(terminal::Color::Black),
-        'R' => return Some(terminal::Color::DarkRed),
-        'G' => return Some(terminal::Color::DarkGreen),
-        'Y' => return Some(terminal::Color::DarkYellow),
-        'B' => return Some(terminal::Color::DarkBlue),
-        'M' => return Some(terminal::Color::DarkMagenta),
-        'C' => return Some(terminal::Color::DarkCyan),
-        'W' => return Some(terminal::Color::Grey),
-
-        _ => return None,
+    if json_string.as_str().unwrap().chars().count() != target_size {
+        return Err(ByofishError::LengthMismatch(String::from(pointer)));
     }
+    return Ok(());
 }
 
 pub fn glyph_from_animation(
-    anim: &Vec<Vec<Vec<ColorGlyph>>>,
+    anim: &[Vec<Vec<ColorGlyph>>],
     frame_idx: usize,
     row_idx: usize,
     glyph_idx: usize,
@@ -205,7 +226,7 @@ pub fn glyph_from_animation(
 ) -> Option<ColorGlyph> {
     let frame_idx_oob = frame_idx >= anim.len();
     if frame_idx_oob {
-        error(&format!("Attempted to access frame out of bounds"), 1);
+        return None;
     }
     let row_idx_oob = (row_idx < position.y) || (row_idx - position.y >= anim[frame_idx].len());
     if row_idx_oob {
@@ -216,5 +237,95 @@ pub fn glyph_from_animation(
     if glyph_idx_oob {
         return None;
     }
-    return Some(anim[frame_idx][row_idx - position.y][glyph_idx - position.x].clone());
+    return Some(anim[frame_idx][row_idx - position.y][glyph_idx - position.x]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_durations(durations: serde_json::Value) -> serde_json::Value {
+        return json!({ "animation": { "durations": durations } });
+    }
+
+    #[test]
+    fn missing_durations_are_all_unset() {
+        let durations = load_durations(&json!({ "animation": {} }), "/animation", 2).unwrap();
+        assert_eq!(durations, vec![None, None]);
+    }
+
+    #[test]
+    fn null_durations_are_unset() {
+        let json = with_durations(json!([100, null]));
+        let durations = load_durations(&json, "/animation", 2).unwrap();
+        assert_eq!(durations, vec![Some(100), None]);
+    }
+
+    #[test]
+    fn rejects_zero_and_negative_durations() {
+        for duration in [json!(0), json!(-5), json!(1.5), json!("100")].iter() {
+            let json = with_durations(json!([100, duration]));
+            let error = load_durations(&json, "/animation", 2).err().unwrap();
+            assert_eq!(
+                error.to_string(),
+                "/animation/durations/1 is not a positive number of milliseconds"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_durations_that_are_not_one_per_frame() {
+        for durations in [json!([100]), json!([100, 100, 100])].iter() {
+            let json = with_durations(durations.clone());
+            let error = load_durations(&json, "/animation", 2).err().unwrap();
+            assert_eq!(error.to_string(), "/animation/durations differs in length");
+        }
+        let error = load_durations(&with_durations(json!(100)), "/animation", 1)
+            .err()
+            .unwrap();
+        assert_eq!(error.to_string(), "/animation/durations is not an array");
+    }
+
+    fn with_symbols(line: &str, colors: &str) -> serde_json::Value {
+        return json!({
+            "animation": {
+                "symbols": [[line]],
+                "colors": [[colors]],
+                "highlights": [[colors]],
+            }
+        });
+    }
+
+    #[test]
+    fn loads_glyphs_longer_than_a_byte() {
+        let animation = load_animation(&with_symbols("°≈┏>", "   r"), "/animation").unwrap();
+        let glyphs: String = animation[0][0]
+            .iter()
+            .map(|color_glyph| color_glyph.glyph)
+            .collect();
+        assert_eq!(glyphs, "°≈┏>");
+        assert_eq!(animation[0][0][3].foreground_color, match_color('r'));
+    }
+
+    #[test]
+    fn counts_glyphs_not_bytes() {
+        let error = load_animation(&with_symbols("°≈", "      "), "/animation")
+            .err()
+            .unwrap();
+        assert_eq!(error.to_string(), "/animation/colors/0/0 differs in length");
+    }
+
+    #[test]
+    fn rejects_glyphs_that_are_not_one_cell_wide() {
+        for (line, glyph) in [("<🐟", '🐟'), ("a\u{301}", '\u{301}'), ("x⚡", '⚡')].iter() {
+            let json = with_symbols(line, &" ".repeat(line.chars().count()));
+            match load_animation(&json, "/animation") {
+                Err(ByofishError::WideGlyph(pointer, wide)) => {
+                    assert_eq!(pointer, "/animation/symbols/0/0");
+                    assert_eq!(wide, *glyph);
+                }
+                _ => panic!("{:?} should not load", line),
+            }
+        }
+    }
 }
@@ -1,43 +1,201 @@
-use animation::{blank_animation, load_animation, Animation, Position, Size};
+use animation::{
+    blank_animation, load_animation, load_durations, Animation, Durations, Position, Size,
+};
+use asset_type::AssetType;
 use color_glyph::EMPTY_COLOR_GLYPH;
-use color_glyph::{color_to_char, ColorGlyph};
+use color_glyph::{color_to_char, Color, ColorGlyph};
 use command;
-use input::Direction;
-use open_json::open_json;
+use direction::Direction;
+use error::ByofishError;
+use fill::{fill_region, FillMatch, Layer};
+use history::{EditKind, History, Saved, Scope, Snapshot};
+use mirror::MirrorTable;
+use open_json::{open_json, save_json};
+use selection::{Clipboard, Rect};
 use serde_json::json;
-use std::io::stdout;
+use shapes;
+use shapes::Shape;
 use std::path::PathBuf;
-use terminal;
+use transform::{empty_border, shift_frame, Border};
+use width::is_single_width;
+
+// milliseconds a frame is shown for when nothing else says
+pub const DEFAULT_DURATION: u64 = 250;
+// how much a frame's duration changes with each press
+const DURATION_STEP: u64 = 50;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Facing {
+    Forward,
+    Flipped,
+}
 
 pub struct Asset {
     // this probably shouldn't be public
-    animation: Animation,
+    forward_animation: Animation,
+    flipped_animation: Animation,
+    forward_durations: Durations,
+    flipped_durations: Durations,
+    // used for frames that don't have their own duration
+    default_duration: u64,
+    // which of the animations is being edited
+    facing: Facing,
     cursor_position: Position,
     current_frame: usize,
+    // true when there are edits that have not been saved
+    dirty: bool,
+    history: History,
+    mirror_table: MirrorTable,
+    // the selection spans from here to the cursor
+    selection_anchor: Option<Position>,
+    clipboard: Option<Clipboard>,
+    // pasting skips empty cells so they don't cover what is underneath
+    transparent_paste: bool,
+    // the other end of a range of frames, the current frame is the first end
+    frame_mark: Option<usize>,
+    // the last glyph and colors that were put down or picked up with the
+    // eyedropper, used by fills, shapes and stamping
+    brush: ColorGlyph,
+    fill_match: FillMatch,
+    // typing past the end of a line carries on at the start of the next one
+    text_wrap: bool,
+    // shifting drops what goes past the edge instead of wrapping it around
+    shift_clip: bool,
+    // the file as it was loaded, saving writes the animations back into it so
+    // keys the editor doesn't know about are kept
+    json: serde_json::Value,
 }
 
 impl Asset {
-    pub fn new(path: &PathBuf, name: &str) -> Asset {
+    pub fn new(path: &PathBuf) -> Result<Asset, ByofishError> {
         // make so you give a path and it opens the file
         //   then it lists the animations and lets you cycle through them with like
         //   page up or down or somthing
         //
         // hardcode to look for forward/flipped_animation as well as
         //   foreground / background animation
-        let anim_json = open_json(path, name, "fish");
-        let anim: Animation = load_animation(&anim_json, "test fish", "/forward_animation");
+        let anim_json = open_json(path)?;
+        return Asset::from_json(&anim_json);
+    }
+
+    pub fn from_json(anim_json: &serde_json::Value) -> Result<Asset, ByofishError> {
+        let forward: Animation = load_animation(anim_json, "/forward_animation")?;
+        let forward_durations = load_durations(anim_json, "/forward_animation", forward.len())?;
+        // older files may only have the forward animation
+        let (flipped, flipped_durations) = if anim_json.pointer("/flipped_animation").is_some() {
+            let flipped = load_animation(anim_json, "/flipped_animation")?;
+            // resizing changes both at once, so they have to start the same size
+            if frame_size(&flipped) != frame_size(&forward) {
+                return Err(ByofishError::LengthMismatch(String::from(
+                    "/flipped_animation",
+                )));
+            }
+            let flipped_durations = load_durations(anim_json, "/flipped_animation", flipped.len())?;
+            (flipped, flipped_durations)
+        } else {
+            (forward.clone(), forward_durations.clone())
+        };
+        let mut asset = Asset::from_animations(forward, flipped, false);
+        asset.forward_durations = forward_durations;
+        asset.flipped_durations = flipped_durations;
+        asset.json = anim_json.clone();
+        return Ok(asset);
+    }
+
+    // starts an asset that does not have a file yet
+    pub fn new_blank(size: Size) -> Asset {
+        return Asset::from_animations(blank_animation(size), blank_animation(size), true);
+    }
+
+    fn from_animations(forward: Animation, flipped: Animation, dirty: bool) -> Asset {
         return Asset {
-            animation: anim,
+            forward_durations: vec![None; forward.len()],
+            flipped_durations: vec![None; flipped.len()],
+            default_duration: DEFAULT_DURATION,
+            forward_animation: forward,
+            flipped_animation: flipped,
+            facing: Facing::Forward,
             cursor_position: Position { x: 0, y: 0 },
             current_frame: 0,
+            dirty,
+            history: History::new(),
+            mirror_table: MirrorTable::new(),
+            selection_anchor: None,
+            clipboard: None,
+            transparent_paste: false,
+            frame_mark: None,
+            brush: EMPTY_COLOR_GLYPH,
+            fill_match: FillMatch::Glyph,
+            text_wrap: false,
+            shift_clip: false,
+            json: json!({}),
         };
     }
 
+    // used when generating the flipped animation
+    pub fn set_mirror_table(&mut self, mirror_table: MirrorTable) {
+        self.mirror_table = mirror_table;
+    }
+
+    // how long frames without their own duration are shown, in milliseconds
+    pub fn set_default_duration(&mut self, duration: u64) {
+        self.default_duration = duration;
+    }
+
+    fn animation(&self) -> &Animation {
+        return self.get_animation(self.facing);
+    }
+
+    fn animation_mut(&mut self) -> &mut Animation {
+        let facing = self.facing;
+        return self.animation_for_mut(facing);
+    }
+
+    fn animation_for_mut(&mut self, facing: Facing) -> &mut Animation {
+        match facing {
+            Facing::Forward => return &mut self.forward_animation,
+            Facing::Flipped => return &mut self.flipped_animation,
+        }
+    }
+
+    fn durations_mut(&mut self) -> &mut Durations {
+        let facing = self.facing;
+        return self.durations_for_mut(facing);
+    }
+
+    fn durations_for_mut(&mut self, facing: Facing) -> &mut Durations {
+        match facing {
+            Facing::Forward => return &mut self.forward_durations,
+            Facing::Flipped => return &mut self.flipped_durations,
+        }
+    }
+
+    fn get_durations(&self, facing: Facing) -> &Durations {
+        match facing {
+            Facing::Forward => return &self.forward_durations,
+            Facing::Flipped => return &self.flipped_durations,
+        }
+    }
+
+    // how long the current frame is shown, in milliseconds
+    pub fn get_frame_duration(&self) -> u64 {
+        return self.get_durations(self.facing)[self.current_frame]
+            .unwrap_or(self.default_duration);
+    }
+
     pub fn get_size(&self) -> Size {
-        return Size {
-            width: self.animation[0][0].len(),
-            height: self.animation[0].len(),
-        };
+        return frame_size(self.animation());
+    }
+
+    pub fn get_animation(&self, facing: Facing) -> &Animation {
+        match facing {
+            Facing::Forward => return &self.forward_animation,
+            Facing::Flipped => return &self.flipped_animation,
+        }
+    }
+
+    pub fn get_facing(&self) -> Facing {
+        return self.facing;
     }
 
     pub fn get_frame_idx(&self) -> usize {
@@ -45,84 +203,351 @@ impl Asset {
     }
 
     pub fn get_frame_num(&self) -> usize {
-        return self.animation.len();
+        return self.animation().len();
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        return self.dirty;
     }
 
     pub fn get_cursor_position(&self) -> Position {
         return self.cursor_position;
     }
 
-    pub fn print(&self, show_cursor: bool) {
-        let frame_idx = self.current_frame;
-        for line_idx in 0..self.get_size().height {
-            // print top line
-            if line_idx == 0 {
-                terminal::set_foreground_color(terminal::Color::Default);
-                terminal::set_background_color(terminal::Color::Default);
-                print!("┏{}┓ \r\n", "━".repeat(self.get_size().width));
-            }
-            for glyph_idx in 0..self.get_size().width {
-                if glyph_idx == 0 {
-                    terminal::set_foreground_color(terminal::Color::Default);
-                    terminal::set_background_color(terminal::Color::Default);
-                    print!("┃");
-                }
-                let pos = Position {
-                    x: glyph_idx,
-                    y: line_idx,
-                };
-                // TODO make cursor flashing
-                if pos == self.cursor_position && show_cursor {
-                    ColorGlyph {
-                        glyph: 'X',
-                        foreground_color: None,
-                        background_color: None,
-                    }
-                    .print();
-                } else {
-                    self.animation[frame_idx][line_idx][glyph_idx].print();
-                }
-                if glyph_idx == self.get_size().width - 1 {
-                    terminal::set_foreground_color(terminal::Color::Default);
-                    terminal::set_background_color(terminal::Color::Default);
-                    print!("┃ ");
-                }
-            }
-            print!("\r\n");
-            // print bottom line
-            if line_idx == self.get_size().height - 1 {
-                print!("┗{}┛ \r\n", "━".repeat(self.get_size().width));
-            }
-        }
+    pub fn get_selection(&self) -> Option<Rect> {
+        let anchor = self.selection_anchor?;
+        return Some(Rect::from_corners(anchor, self.cursor_position));
+    }
+
+    pub fn is_transparent_paste(&self) -> bool {
+        return self.transparent_paste;
+    }
+
+    pub fn get_frame_mark(&self) -> Option<usize> {
+        return self.frame_mark;
+    }
+
+    pub fn get_brush(&self) -> ColorGlyph {
+        return self.brush;
+    }
+
+    pub fn is_text_wrap(&self) -> bool {
+        return self.text_wrap;
+    }
+
+    pub fn is_shift_clip(&self) -> bool {
+        return self.shift_clip;
+    }
+
+    pub fn get_fill_match(&self) -> FillMatch {
+        return self.fill_match;
     }
 
     pub fn handle_command(&mut self, cmd: &command::Command) {
         match cmd {
             command::Command::MoveCursor(direction) => {
+                self.history.break_run();
                 self.move_cursor(direction);
             }
             command::Command::Resize(direction, magnitude) => {
-                self.resize(direction, *magnitude);
+                self.edit(EditKind::Other, Scope::Both, |asset| {
+                    asset.resize(direction, *magnitude)
+                });
+            }
+            // glyphs that aren't one cell wide would throw off the grid
+            command::Command::SetChar(character) if is_single_width(*character) => {
+                self.edit(EditKind::Glyph, self.frame_scope(), |asset| {
+                    asset.set_char(*character)
+                });
+            }
+            command::Command::TypeChar(character) if is_single_width(*character) => {
+                self.edit(EditKind::Glyph, self.frame_scope(), |asset| {
+                    asset.type_char(*character)
+                });
+            }
+            command::Command::Backspace => {
+                self.edit(EditKind::Glyph, self.frame_scope(), |asset| {
+                    asset.backspace()
+                });
+            }
+            command::Command::NewLine => {
+                self.history.break_run();
+                self.new_line();
+            }
+            command::Command::LineStart => {
+                self.history.break_run();
+                self.cursor_position.x = 0;
+            }
+            command::Command::LineEnd => {
+                self.history.break_run();
+                self.cursor_position.x = self.get_size().width - 1;
+            }
+            command::Command::FlipHorizontal(all_frames) => {
+                self.edit(
+                    EditKind::Other,
+                    self.transform_scope(*all_frames),
+                    |asset| asset.flip(false, *all_frames),
+                );
+            }
+            command::Command::FlipVertical(all_frames) => {
+                self.edit(
+                    EditKind::Other,
+                    self.transform_scope(*all_frames),
+                    |asset| asset.flip(true, *all_frames),
+                );
             }
-            command::Command::SetChar(character) => {
-                self.set_char(*character);
+            command::Command::Rotate(all_frames) => {
+                self.edit(
+                    EditKind::Other,
+                    self.transform_scope(*all_frames),
+                    |asset| asset.rotate(*all_frames),
+                );
+            }
+            command::Command::Shift(direction) => {
+                self.edit(EditKind::Other, self.animation_scope(), |asset| {
+                    asset.shift(direction)
+                });
+            }
+            command::Command::ToggleShiftClip => {
+                self.shift_clip = !self.shift_clip;
+            }
+            command::Command::Trim => {
+                self.edit(EditKind::Other, Scope::Both, |asset| asset.trim());
+            }
+            command::Command::ToggleTextWrap => {
+                self.text_wrap = !self.text_wrap;
             }
             command::Command::SetColor(color) => {
-                self.set_color(&color);
+                self.edit(EditKind::Color, self.frame_scope(), |asset| {
+                    asset.set_color(color)
+                });
+            }
+            command::Command::SetHighlight(color) => {
+                self.edit(EditKind::Highlight, self.frame_scope(), |asset| {
+                    asset.set_highlight(color)
+                });
             }
             command::Command::AddFrame => {
-                self.add_frame();
+                self.edit(EditKind::Other, self.animation_scope(), |asset| {
+                    asset.add_frame()
+                });
             }
             command::Command::DeleteFrame => {
-                self.delete_frame();
+                self.edit(EditKind::Other, self.animation_scope(), |asset| {
+                    asset.delete_frame()
+                });
+            }
+            command::Command::DuplicateFrame => {
+                self.edit(EditKind::Other, self.animation_scope(), |asset| {
+                    asset.duplicate_frame()
+                });
+            }
+            command::Command::MoveFrame(delta) => {
+                self.edit(EditKind::Other, self.animation_scope(), |asset| {
+                    asset.move_frame(*delta)
+                });
+            }
+            command::Command::ChangeDuration(steps) => {
+                self.edit(EditKind::Other, self.animation_scope(), |asset| {
+                    asset.change_duration(*steps)
+                });
+            }
+            command::Command::SwapFrames => {
+                self.edit(EditKind::Other, self.animation_scope(), |asset| {
+                    asset.swap_with_marked_frame()
+                });
+            }
+            command::Command::GenerateFlipped => {
+                self.edit(
+                    EditKind::Other,
+                    Scope::Animation(Facing::Flipped),
+                    |asset| asset.generate_flipped(),
+                );
             }
             command::Command::CycleFrame(magnitude) => {
+                self.history.break_run();
                 self.cycle_frame(*magnitude);
             }
+            command::Command::SwitchFacing => {
+                self.history.break_run();
+                self.switch_facing();
+            }
+            command::Command::ToggleSelection => {
+                self.history.break_run();
+                self.toggle_selection();
+            }
+            command::Command::Copy => {
+                self.history.break_run();
+                self.copy_selection();
+            }
+            command::Command::Cut => {
+                self.edit(EditKind::Other, self.frame_scope(), |asset| {
+                    asset.cut_selection()
+                });
+            }
+            command::Command::Paste => {
+                self.edit(EditKind::Other, self.frame_scope(), |asset| asset.paste());
+            }
+            command::Command::MarkFrame => {
+                self.history.break_run();
+                self.toggle_frame_mark();
+            }
+            command::Command::ApplyToAllFrames => {
+                self.edit(EditKind::Other, self.animation_scope(), |asset| {
+                    asset.apply_to_all_frames()
+                });
+            }
+            command::Command::ApplyToFrameRange => {
+                self.edit(EditKind::Other, self.animation_scope(), |asset| {
+                    asset.apply_to_frame_range()
+                });
+            }
+            command::Command::Fill(layer) => {
+                self.edit(EditKind::Other, self.frame_scope(), |asset| {
+                    asset.fill(layer)
+                });
+            }
+            command::Command::DrawShape(shape) => {
+                self.edit(EditKind::Other, self.frame_scope(), |asset| {
+                    asset.draw_shape(shape)
+                });
+            }
+            command::Command::PickBrush => {
+                self.history.break_run();
+                self.pick_brush();
+            }
+            command::Command::StampBrush => {
+                self.edit(EditKind::Other, self.frame_scope(), |asset| {
+                    asset.stamp_brush()
+                });
+            }
+            command::Command::CycleFillMatch => {
+                self.fill_match = self.fill_match.next();
+            }
+            command::Command::ToggleTransparentPaste => {
+                self.transparent_paste = !self.transparent_paste;
+            }
+            command::Command::Undo => {
+                self.undo();
+            }
+            command::Command::Redo => {
+                self.redo();
+            }
             _ => {}
         }
     }
 
+    // applies an edit so it can be undone, only the part of the asset the
+    // edit can reach is kept and compared, edits that turn out to change
+    // nothing leave the history and the saved state alone
+    fn edit<F: FnOnce(&mut Asset)>(&mut self, kind: EditKind, scope: Scope, apply: F) {
+        let before = self.snapshot(scope);
+        apply(self);
+        if self.saved(scope) != before.saved {
+            self.history.record(before, kind);
+            self.dirty = true;
+        }
+    }
+
+    // edits that only reach the current frame
+    fn frame_scope(&self) -> Scope {
+        return Scope::Frame(self.facing, self.current_frame);
+    }
+
+    // edits that add, remove or reorder frames or change durations
+    fn animation_scope(&self) -> Scope {
+        return Scope::Animation(self.facing);
+    }
+
+    fn transform_scope(&self, all_frames: bool) -> Scope {
+        if all_frames {
+            return self.animation_scope();
+        }
+        return self.frame_scope();
+    }
+
+    fn saved(&self, scope: Scope) -> Saved {
+        match scope {
+            Scope::Frame(facing, frame_idx) => {
+                return Saved::Frame(
+                    facing,
+                    frame_idx,
+                    self.get_animation(facing)[frame_idx].clone(),
+                );
+            }
+            Scope::Animation(facing) => {
+                return Saved::Animation(
+                    facing,
+                    self.get_animation(facing).clone(),
+                    self.get_durations(facing).clone(),
+                );
+            }
+            Scope::Both => {
+                return Saved::Both(
+                    self.forward_animation.clone(),
+                    self.forward_durations.clone(),
+                    self.flipped_animation.clone(),
+                    self.flipped_durations.clone(),
+                );
+            }
+        }
+    }
+
+    fn snapshot(&self, scope: Scope) -> Snapshot {
+        return Snapshot {
+            saved: self.saved(scope),
+            facing: self.facing,
+            cursor_position: self.cursor_position,
+            current_frame: self.current_frame,
+        };
+    }
+
+    fn restore(&mut self, snapshot: Snapshot) {
+        match snapshot.saved {
+            Saved::Frame(facing, frame_idx, frame) => {
+                self.animation_for_mut(facing)[frame_idx] = frame;
+            }
+            Saved::Animation(facing, animation, durations) => {
+                *self.animation_for_mut(facing) = animation;
+                *self.durations_for_mut(facing) = durations;
+            }
+            Saved::Both(
+                forward_animation,
+                forward_durations,
+                flipped_animation,
+                flipped_durations,
+            ) => {
+                self.forward_animation = forward_animation;
+                self.forward_durations = forward_durations;
+                self.flipped_animation = flipped_animation;
+                self.flipped_durations = flipped_durations;
+            }
+        }
+        self.facing = snapshot.facing;
+        self.cursor_position = snapshot.cursor_position;
+        self.current_frame = snapshot.current_frame;
+        self.dirty = true;
+        self.clamp_frame();
+    }
+
+    pub fn undo(&mut self) {
+        if let Some(scope) = self.history.undo_scope() {
+            let current = self.snapshot(scope);
+            if let Some(previous) = self.history.undo(current) {
+                self.restore(previous);
+            }
+        }
+    }
+
+    pub fn redo(&mut self) {
+        if let Some(scope) = self.history.redo_scope() {
+            let current = self.snapshot(scope);
+            if let Some(next) = self.history.redo(current) {
+                self.restore(next);
+            }
+        }
+    }
+
     pub fn move_cursor(&mut self, direction: &Direction) {
         match direction {
             Direction::Left => {
@@ -151,80 +576,51 @@ impl Asset {
     pub fn resize(&mut self, direction: &Direction, delta: isize) {
         let delta_abs = delta.abs();
         let grow = delta.is_positive();
+        // the other animation faces the opposite way, so its sides are swapped
+        let mirrored_direction = match direction {
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+            _ => *direction,
+        };
 
         for _i in 0..delta_abs {
-            // change the size
-            // size should just be a function shouldn't it
-            if *direction == Direction::Up || *direction == Direction::Down {
-                if grow {
-                    self.get_size().height += 1;
-                } else {
-                    // so it doesn't shrink to nothing
-                    if self.get_size().height <= 1 {
-                        break;
-                    }
-                    self.get_size().height -= 1;
-                }
-            } else {
-                // else must be left or right
-                if grow {
-                    self.get_size().width += 1;
-                } else {
-                    // so it doesn't shrink to nothing
-                    if self.get_size().width <= 1 {
+            // so neither animation shrinks to nothing
+            if !grow {
+                let forward_size = frame_size(&self.forward_animation);
+                let flipped_size = frame_size(&self.flipped_animation);
+                if *direction == Direction::Up || *direction == Direction::Down {
+                    if forward_size.height.min(flipped_size.height) <= 1 {
                         break;
                     }
-                    self.get_size().width -= 1;
+                } else if forward_size.width.min(flipped_size.width) <= 1 {
+                    break;
                 }
             }
-            // this could be inverted and it might be better
-            for frame_idx in 0..self.animation.len() {
-                if *direction == Direction::Up {
-                    if grow {
-                        let line_len = self.animation[frame_idx][0].len();
-                        self.animation[frame_idx].insert(0, vec![EMPTY_COLOR_GLYPH; line_len]);
-                        // cursor moves naturally with growth
-                        if frame_idx == 0 {
-                            self.cursor_position.y += 1;
-                        }
-                    } else {
-                        self.animation[frame_idx].remove(0);
-                    }
-                } else if *direction == Direction::Down {
-                    if grow {
-                        let line_len = self.animation[frame_idx][0].len();
-                        self.animation[frame_idx].push(vec![EMPTY_COLOR_GLYPH; line_len]);
-                    } else {
-                        self.animation[frame_idx].pop();
-                    }
+            match self.facing {
+                Facing::Forward => {
+                    resize_animation(&mut self.forward_animation, direction, grow);
+                    resize_animation(&mut self.flipped_animation, &mirrored_direction, grow);
                 }
-                for line_idx in 0..self.animation[frame_idx].len() {
-                    if *direction == Direction::Left {
-                        if grow {
-                            self.animation[frame_idx][line_idx].insert(0, EMPTY_COLOR_GLYPH);
-                            // cursor moves naturally with growth
-                            if line_idx == 0 && frame_idx == 0 {
-                                self.cursor_position.x += 1;
-                            }
-                        } else {
-                            self.animation[frame_idx][line_idx].remove(0);
-                            // this is a sign this code sucks
-                            if line_idx == 0 && frame_idx == 0 {
-                                if self.cursor_position.x != 0 {
-                                    self.cursor_position.x -= 1;
-                                }
-                            }
-                        }
-                    } else if *direction == Direction::Right {
-                        if grow {
-                            self.animation[frame_idx][line_idx].push(EMPTY_COLOR_GLYPH);
-                        } else {
-                            self.animation[frame_idx][line_idx].pop();
-                        }
-                    }
+                Facing::Flipped => {
+                    resize_animation(&mut self.flipped_animation, direction, grow);
+                    resize_animation(&mut self.forward_animation, &mirrored_direction, grow);
+                }
+            }
+            // cursor moves naturally with growth
+            if grow && *direction == Direction::Up {
+                self.cursor_position.y += 1;
+            } else if *direction == Direction::Left {
+                if grow {
+                    self.cursor_position.x += 1;
+                } else if self.cursor_position.x != 0 {
+                    self.cursor_position.x -= 1;
                 }
             }
         }
+        self.clamp_cursor();
+    }
+
+    fn clamp_cursor(&mut self) {
         let asset_size: Size = self.get_size();
         if self.cursor_position.x >= asset_size.width {
             self.cursor_position.x = asset_size.width - 1;
@@ -232,24 +628,312 @@ impl Asset {
         if self.cursor_position.y >= asset_size.height {
             self.cursor_position.y = asset_size.height - 1;
         }
+        if let Some(ref mut anchor) = self.selection_anchor {
+            anchor.x = anchor.x.min(asset_size.width - 1);
+            anchor.y = anchor.y.min(asset_size.height - 1);
+        }
     }
 
     pub fn set_char(&mut self, character: char) {
+        self.put_glyph(character);
+        self.brush.glyph = character;
+    }
+
+    fn put_glyph(&mut self, character: char) {
         let frame_idx = self.current_frame;
         let line_idx = self.cursor_position.y;
         let character_idx = self.cursor_position.x;
-        let mut color_glyph = self.animation[frame_idx][line_idx][character_idx];
+        let mut color_glyph = self.animation()[frame_idx][line_idx][character_idx];
         color_glyph.glyph = character;
-        self.animation[frame_idx][line_idx][character_idx] = color_glyph;
+        self.animation_mut()[frame_idx][line_idx][character_idx] = color_glyph;
     }
 
-    pub fn set_color(&mut self, color: &terminal::Color) {
+    pub fn type_char(&mut self, character: char) {
+        self.set_char(character);
+        let size = self.get_size();
+        if self.cursor_position.x + 1 < size.width {
+            self.cursor_position.x += 1;
+        } else if self.text_wrap && self.cursor_position.y + 1 < size.height {
+            self.cursor_position.x = 0;
+            self.cursor_position.y += 1;
+        }
+    }
+
+    // steps back a cell and clears it
+    pub fn backspace(&mut self) {
+        if self.cursor_position.x >= 1 {
+            self.cursor_position.x -= 1;
+        } else if self.text_wrap && self.cursor_position.y >= 1 {
+            self.cursor_position.x = self.get_size().width - 1;
+            self.cursor_position.y -= 1;
+        } else {
+            return;
+        }
+        self.put_glyph(' ');
+    }
+
+    pub fn new_line(&mut self) {
+        if self.cursor_position.y + 1 < self.get_size().height {
+            self.cursor_position.y += 1;
+        }
+        self.cursor_position.x = 0;
+    }
+
+    pub fn set_color(&mut self, color: &Option<Color>) {
         let frame_idx = self.current_frame;
         let line_idx = self.cursor_position.y;
         let glyph_idx = self.cursor_position.x;
-        let mut color_glyph = self.animation[frame_idx][line_idx][glyph_idx];
-        color_glyph.foreground_color = Some(*color);
-        self.animation[frame_idx][line_idx][glyph_idx] = color_glyph;
+        let mut color_glyph = self.animation()[frame_idx][line_idx][glyph_idx];
+        color_glyph.foreground_color = *color;
+        self.animation_mut()[frame_idx][line_idx][glyph_idx] = color_glyph;
+        self.brush.foreground_color = *color;
+    }
+
+    pub fn set_highlight(&mut self, color: &Option<Color>) {
+        let frame_idx = self.current_frame;
+        let line_idx = self.cursor_position.y;
+        let glyph_idx = self.cursor_position.x;
+        let mut color_glyph = self.animation()[frame_idx][line_idx][glyph_idx];
+        color_glyph.background_color = *color;
+        self.animation_mut()[frame_idx][line_idx][glyph_idx] = color_glyph;
+        self.brush.background_color = *color;
+    }
+
+    // eyedropper, the brush becomes the cell under the cursor
+    pub fn pick_brush(&mut self) {
+        let pos = self.cursor_position;
+        self.brush = self.animation()[self.current_frame][pos.y][pos.x];
+    }
+
+    // puts the brush's glyph and both colors down at the cursor
+    pub fn stamp_brush(&mut self) {
+        let frame_idx = self.current_frame;
+        let pos = self.cursor_position;
+        let brush = self.brush;
+        self.animation_mut()[frame_idx][pos.y][pos.x] = brush;
+    }
+
+    // fills the region under the cursor with the brush's glyph or one of its colors
+    pub fn fill(&mut self, layer: &Layer) {
+        let frame_idx = self.current_frame;
+        let region = fill_region(
+            &self.animation()[frame_idx],
+            self.cursor_position,
+            self.fill_match,
+        );
+        let brush = self.brush;
+        let frame = &mut self.animation_mut()[frame_idx];
+        for pos in region {
+            let color_glyph = &mut frame[pos.y][pos.x];
+            match layer {
+                Layer::Glyph => color_glyph.glyph = brush.glyph,
+                Layer::Foreground => color_glyph.foreground_color = brush.foreground_color,
+                Layer::Background => color_glyph.background_color = brush.background_color,
+            }
+        }
+    }
+
+    // draws between the selection anchor and the cursor with the brush, lines
+    // and boxes pick their own glyphs
+    pub fn draw_shape(&mut self, shape: &Shape) {
+        let anchor = match self.selection_anchor {
+            Some(anchor) => anchor,
+            None => return,
+        };
+        let rect = Rect::from_corners(anchor, self.cursor_position);
+        let cells: Vec<(Position, char)> = match shape {
+            Shape::Line => shapes::line(anchor, self.cursor_position),
+            Shape::Rectangle => shapes::rectangle(rect)
+                .into_iter()
+                .map(|pos| (pos, self.brush.glyph))
+                .collect(),
+            Shape::Box => shapes::box_border(rect),
+            Shape::Ellipse => shapes::ellipse(rect)
+                .into_iter()
+                .map(|pos| (pos, self.brush.glyph))
+                .collect(),
+        };
+        let brush = self.brush;
+        let frame_idx = self.current_frame;
+        let frame = &mut self.animation_mut()[frame_idx];
+        for (pos, glyph) in cells {
+            frame[pos.y][pos.x] = ColorGlyph { glyph, ..brush };
+        }
+        self.selection_anchor = None;
+    }
+
+    pub fn toggle_selection(&mut self) {
+        if self.selection_anchor.is_some() {
+            self.selection_anchor = None;
+        } else {
+            self.selection_anchor = Some(self.cursor_position);
+        }
+    }
+
+    // copies the selection, or just the cell under the cursor when nothing is selected
+    pub fn copy_selection(&mut self) {
+        let rect = self
+            .get_selection()
+            .unwrap_or_else(|| Rect::from_corners(self.cursor_position, self.cursor_position));
+        let frame = &self.animation()[self.current_frame];
+        let mut cells: Vec<Vec<ColorGlyph>> = Vec::new();
+        for line in frame.iter().skip(rect.position.y).take(rect.size.height) {
+            cells.push(line[rect.position.x..rect.position.x + rect.size.width].to_vec());
+        }
+        self.clipboard = Some(Clipboard {
+            cells,
+            origin: rect.position,
+        });
+        self.selection_anchor = None;
+    }
+
+    pub fn cut_selection(&mut self) {
+        let rect = self
+            .get_selection()
+            .unwrap_or_else(|| Rect::from_corners(self.cursor_position, self.cursor_position));
+        self.copy_selection();
+        let frame_idx = self.current_frame;
+        let frame = &mut self.animation_mut()[frame_idx];
+        for line in frame
+            .iter_mut()
+            .skip(rect.position.y)
+            .take(rect.size.height)
+        {
+            for color_glyph in line.iter_mut().skip(rect.position.x).take(rect.size.width) {
+                *color_glyph = EMPTY_COLOR_GLYPH;
+            }
+        }
+    }
+
+    // pastes the clipboard with its top left corner at the cursor
+    pub fn paste(&mut self) {
+        let frame_idx = self.current_frame;
+        let position = self.cursor_position;
+        self.stamp_clipboard(frame_idx, position);
+    }
+
+    pub fn toggle_frame_mark(&mut self) {
+        if self.frame_mark == Some(self.current_frame) {
+            self.frame_mark = None;
+        } else {
+            self.frame_mark = Some(self.current_frame);
+        }
+    }
+
+    pub fn apply_to_all_frames(&mut self) {
+        let last_frame = self.get_frame_num() - 1;
+        self.apply_to_frames(0, last_frame);
+    }
+
+    // applies to every frame between the marked one and the current one
+    pub fn apply_to_frame_range(&mut self) {
+        if let Some(mark) = self.frame_mark {
+            let current = self.current_frame;
+            self.apply_to_frames(mark.min(current), mark.max(current));
+        }
+    }
+
+    // stamps the selection (or the clipboard if nothing is selected) at the
+    // spot it was copied from in each of the frames
+    fn apply_to_frames(&mut self, first_frame: usize, last_frame: usize) {
+        if self.selection_anchor.is_some() {
+            self.copy_selection();
+        }
+        let origin = match self.clipboard {
+            Some(ref clipboard) => clipboard.origin,
+            None => return,
+        };
+        for frame_idx in first_frame..=last_frame {
+            self.stamp_clipboard(frame_idx, origin);
+        }
+    }
+
+    fn stamp_clipboard(&mut self, frame_idx: usize, position: Position) {
+        let clipboard = match self.clipboard {
+            Some(ref clipboard) => clipboard.clone(),
+            None => return,
+        };
+        let transparent = self.transparent_paste;
+        let frame = &mut self.animation_mut()[frame_idx];
+        // anything past the edge of the frame is dropped
+        for (line, cells) in frame
+            .iter_mut()
+            .skip(position.y)
+            .zip(clipboard.cells.iter())
+        {
+            for (color_glyph, cell) in line.iter_mut().skip(position.x).zip(cells.iter()) {
+                if transparent && cell.glyph == ' ' {
+                    continue;
+                }
+                *color_glyph = *cell;
+            }
+        }
+    }
+
+    // flips the current frame, or every frame, in the animation being edited
+    pub fn flip(&mut self, vertical: bool, all_frames: bool) {
+        let frame_idx = self.current_frame;
+        let mirror_table = &self.mirror_table;
+        let animation = match self.facing {
+            Facing::Forward => &mut self.forward_animation,
+            Facing::Flipped => &mut self.flipped_animation,
+        };
+        for (idx, frame) in animation.iter_mut().enumerate() {
+            if !all_frames && idx != frame_idx {
+                continue;
+            }
+            if vertical {
+                mirror_table.flip_frame(frame);
+            } else {
+                mirror_table.mirror_frame(frame);
+            }
+        }
+    }
+
+    // moves every frame of the animation being edited one cell over
+    pub fn shift(&mut self, direction: &Direction) {
+        let wrap = !self.shift_clip;
+        for frame in self.animation_mut().iter_mut() {
+            shift_frame(frame, direction, wrap);
+        }
+    }
+
+    // turns the current frame, or every frame, half way round, which is the
+    // same as flipping it both ways
+    pub fn rotate(&mut self, all_frames: bool) {
+        self.flip(false, all_frames);
+        self.flip(true, all_frames);
+    }
+
+    // removes the empty lines and columns around the art, both animations
+    // have to stay the same size so only what is empty in both goes
+    pub fn trim(&mut self) {
+        let (animation, other_animation) = match self.facing {
+            Facing::Forward => (&self.forward_animation, &self.flipped_animation),
+            Facing::Flipped => (&self.flipped_animation, &self.forward_animation),
+        };
+        // the other animation faces the opposite way, so its sides are swapped
+        let other_border = empty_border(other_animation).map(|border| Border {
+            left: border.right,
+            right: border.left,
+            ..border
+        });
+        // a blank animation doesn't hold the trim back, but blank art is left
+        // alone rather than trimmed to nothing
+        let border = match (empty_border(animation), other_border) {
+            (Some(border), Some(other_border)) => Border {
+                top: border.top.min(other_border.top),
+                bottom: border.bottom.min(other_border.bottom),
+                left: border.left.min(other_border.left),
+                right: border.right.min(other_border.right),
+            },
+            (Some(border), None) | (None, Some(border)) => border,
+            (None, None) => return,
+        };
+        self.resize(&Direction::Up, -(border.top as isize));
+        self.resize(&Direction::Down, -(border.bottom as isize));
+        self.resize(&Direction::Left, -(border.left as isize));
+        self.resize(&Direction::Right, -(border.right as isize));
     }
 
     pub fn cycle_frame(&mut self, delta: isize) {
@@ -258,79 +942,479 @@ impl Asset {
         self.current_frame = new_frame_idx as usize;
     }
 
+    pub fn switch_facing(&mut self) {
+        self.facing = match self.facing {
+            Facing::Forward => Facing::Flipped,
+            Facing::Flipped => Facing::Forward,
+        };
+        // the mark belongs to the other animation's frames
+        self.frame_mark = None;
+        self.clamp_frame();
+    }
+
+    fn clamp_frame(&mut self) {
+        // the animations don't need the same number of frames
+        if self.current_frame >= self.get_frame_num() {
+            self.current_frame = self.get_frame_num() - 1;
+        }
+        if let Some(mark) = self.frame_mark {
+            if mark >= self.get_frame_num() {
+                self.frame_mark = None;
+            }
+        }
+        self.clamp_cursor();
+    }
+
+    // replaces the flipped animation with a mirrored copy of the forward one
+    pub fn generate_flipped(&mut self) {
+        self.flipped_animation = self.mirror_table.mirror_animation(&self.forward_animation);
+        self.flipped_durations = self.forward_durations.clone();
+        if self.facing == Facing::Flipped {
+            self.clamp_frame();
+        }
+    }
+
     pub fn add_frame(&mut self) {
-        self.animation.insert(
-            self.current_frame,
-            blank_animation(self.get_size())[0].clone(),
-        );
+        let frame_idx = self.current_frame;
+        let blank_frame = blank_animation(self.get_size())[0].clone();
+        self.animation_mut().insert(frame_idx, blank_frame);
+        self.durations_mut().insert(frame_idx, None);
+        // keep the mark on the same frame as it shifts over
+        if let Some(mark) = self.frame_mark {
+            if mark >= frame_idx {
+                self.frame_mark = Some(mark + 1);
+            }
+        }
+    }
+
+    // the copy goes after the current frame and becomes the current frame
+    pub fn duplicate_frame(&mut self) {
+        let frame_idx = self.current_frame;
+        let frame = self.animation()[frame_idx].clone();
+        self.animation_mut().insert(frame_idx + 1, frame);
+        let duration = self.durations_mut()[frame_idx];
+        self.durations_mut().insert(frame_idx + 1, duration);
+        self.current_frame += 1;
+        if let Some(mark) = self.frame_mark {
+            if mark > frame_idx {
+                self.frame_mark = Some(mark + 1);
+            }
+        }
+    }
+
+    // moves the current frame earlier or later in the animation
+    pub fn move_frame(&mut self, delta: isize) {
+        let frame_idx = self.current_frame;
+        let new_frame_idx = frame_idx as isize + delta;
+        if new_frame_idx < 0 || new_frame_idx >= self.get_frame_num() as isize {
+            return;
+        }
+        let new_frame_idx = new_frame_idx as usize;
+        let frame = self.animation_mut().remove(frame_idx);
+        self.animation_mut().insert(new_frame_idx, frame);
+        let duration = self.durations_mut().remove(frame_idx);
+        self.durations_mut().insert(new_frame_idx, duration);
+        self.current_frame = new_frame_idx;
+        // the mark follows its frame
+        if let Some(mark) = self.frame_mark {
+            if mark == frame_idx {
+                self.frame_mark = Some(new_frame_idx);
+            } else if frame_idx < mark && mark <= new_frame_idx {
+                self.frame_mark = Some(mark - 1);
+            } else if new_frame_idx <= mark && mark < frame_idx {
+                self.frame_mark = Some(mark + 1);
+            }
+        }
+    }
+
+    pub fn swap_with_marked_frame(&mut self) {
+        if let Some(mark) = self.frame_mark {
+            let frame_idx = self.current_frame;
+            self.animation_mut().swap(frame_idx, mark);
+            self.durations_mut().swap(frame_idx, mark);
+        }
+    }
+
+    // lengthens or shortens the current frame by steps of DURATION_STEP
+    pub fn change_duration(&mut self, steps: isize) {
+        let duration = self.get_frame_duration() as i64 + steps as i64 * DURATION_STEP as i64;
+        let frame_idx = self.current_frame;
+        self.durations_mut()[frame_idx] = Some(duration.max(DURATION_STEP as i64) as u64);
     }
 
     pub fn delete_frame(&mut self) {
-        if !(self.animation.len() <= 1) {
-            self.animation.remove(self.current_frame);
-            self.current_frame = self.current_frame % self.get_size().height;
+        if self.animation().len() > 1 {
+            let frame_idx = self.current_frame;
+            self.animation_mut().remove(frame_idx);
+            self.durations_mut().remove(frame_idx);
+            self.current_frame %= self.get_frame_num();
+            self.frame_mark = match self.frame_mark {
+                Some(mark) if mark == frame_idx => None,
+                Some(mark) if mark > frame_idx => Some(mark - 1),
+                mark => mark,
+            };
         }
     }
 
-    pub fn export(&mut self) -> serde_json::Value {
-        let size = self.get_size();
-        let num_frames = self.get_frame_num();
-
-        let mut forward_animation_symbols: Vec<Vec<String>> = Vec::new();
-        let mut forward_animation_colors: Vec<Vec<String>> = Vec::new();
-        let mut forward_animation_highlights: Vec<Vec<String>> = Vec::new();
-
-        let mut flipped_animation_symbols: Vec<Vec<String>> = Vec::new();
-        let mut flipped_animation_colors: Vec<Vec<String>> = Vec::new();
-        let mut flipped_animation_highlights: Vec<Vec<String>> = Vec::new();
-
-        // I should learn that fancy functional stuff
-        for frame_idx in 0..num_frames {
-            forward_animation_symbols.push(Vec::new());
-            forward_animation_colors.push(Vec::new());
-            forward_animation_highlights.push(Vec::new());
-
-            flipped_animation_symbols.push(Vec::new());
-            flipped_animation_colors.push(Vec::new());
-            flipped_animation_highlights.push(Vec::new());
-
-            for line_idx in 0..size.height {
-                forward_animation_symbols[frame_idx].push(String::new());
-                forward_animation_colors[frame_idx].push(String::new());
-                forward_animation_highlights[frame_idx].push(String::new());
-
-                flipped_animation_symbols[frame_idx].push(String::new());
-                flipped_animation_colors[frame_idx].push(String::new());
-                flipped_animation_highlights[frame_idx].push(String::new());
-
-                for glyph_idx in 0..size.width {
-                    let color_glyph = self.animation[frame_idx][line_idx][glyph_idx];
-                    forward_animation_symbols[frame_idx][line_idx].push(color_glyph.glyph);
-                    forward_animation_colors[frame_idx][line_idx]
-                        .push(color_to_char(&color_glyph.foreground_color));
-                    forward_animation_highlights[frame_idx][line_idx]
-                        .push(color_to_char(&color_glyph.background_color));
-
-                    flipped_animation_symbols[frame_idx][line_idx].push(color_glyph.glyph);
-                    flipped_animation_colors[frame_idx][line_idx]
-                        .push(color_to_char(&color_glyph.foreground_color));
-                    flipped_animation_highlights[frame_idx][line_idx]
-                        .push(color_to_char(&color_glyph.background_color));
+    pub fn save(&mut self, path: &PathBuf, asset_type: &AssetType) -> Result<(), ByofishError> {
+        let mut json = self.export();
+        asset_type.write_settings(&mut json);
+        save_json(path, &json)?;
+        self.dirty = false;
+        return Ok(());
+    }
+
+    pub fn export(&self) -> serde_json::Value {
+        let mut json = self.json.clone();
+        export_animation(
+            &mut json["forward_animation"],
+            &self.forward_animation,
+            &self.forward_durations,
+        );
+        export_animation(
+            &mut json["flipped_animation"],
+            &self.flipped_animation,
+            &self.flipped_durations,
+        );
+        return json;
+    }
+}
+
+// writes the animation over the one in json, anything else in there is kept
+fn export_animation(json: &mut serde_json::Value, animation: &Animation, durations: &Durations) {
+    let mut symbols: Vec<Vec<String>> = Vec::new();
+    let mut colors: Vec<Vec<String>> = Vec::new();
+    let mut highlights: Vec<Vec<String>> = Vec::new();
+
+    // I should learn that fancy functional stuff
+    for frame in animation {
+        let mut frame_symbols: Vec<String> = Vec::new();
+        let mut frame_colors: Vec<String> = Vec::new();
+        let mut frame_highlights: Vec<String> = Vec::new();
+
+        for line in frame {
+            let mut line_symbols = String::new();
+            let mut line_colors = String::new();
+            let mut line_highlights = String::new();
+
+            for color_glyph in line {
+                line_symbols.push(color_glyph.glyph);
+                line_colors.push(color_to_char(&color_glyph.foreground_color));
+                line_highlights.push(color_to_char(&color_glyph.background_color));
+            }
+            frame_symbols.push(line_symbols);
+            frame_colors.push(line_colors);
+            frame_highlights.push(line_highlights);
+        }
+        symbols.push(frame_symbols);
+        colors.push(frame_colors);
+        highlights.push(frame_highlights);
+    }
+
+    if !json.is_object() {
+        *json = json!({});
+    }
+    json["symbols"] = json!(symbols);
+    json["colors"] = json!(colors);
+    json["highlights"] = json!(highlights);
+    // leave files that never had durations as they were, frames without
+    // one are written as null so they keep following the default
+    if durations.iter().any(|duration| duration.is_some()) {
+        json["durations"] = json!(durations);
+    } else if let Some(object) = json.as_object_mut() {
+        object.remove("durations");
+    }
+}
+
+fn frame_size(animation: &Animation) -> Size {
+    return Size {
+        width: animation[0][0].len(),
+        height: animation[0].len(),
+    };
+}
+
+// adds or removes one line or column on the given side of every frame
+fn resize_animation(animation: &mut Animation, direction: &Direction, grow: bool) {
+    for frame in animation.iter_mut() {
+        let line_len = frame[0].len();
+        match direction {
+            Direction::Up => {
+                if grow {
+                    frame.insert(0, vec![EMPTY_COLOR_GLYPH; line_len]);
+                } else {
+                    frame.remove(0);
+                }
+            }
+            Direction::Down => {
+                if grow {
+                    frame.push(vec![EMPTY_COLOR_GLYPH; line_len]);
+                } else {
+                    frame.pop();
+                }
+            }
+            Direction::Left => {
+                for line in frame.iter_mut() {
+                    if grow {
+                        line.insert(0, EMPTY_COLOR_GLYPH);
+                    } else {
+                        line.remove(0);
+                    }
+                }
+            }
+            Direction::Right => {
+                for line in frame.iter_mut() {
+                    if grow {
+                        line.push(EMPTY_COLOR_GLYPH);
+                    } else {
+                        line.pop();
+                    }
                 }
             }
         }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
+    fn animation_json(line: &str) -> serde_json::Value {
+        let blank = " ".repeat(line.chars().count());
         return json!({
+            "symbols": [[line]],
+            "colors": [[blank]],
+            "highlights": [[blank]],
+        });
+    }
+
+    #[test]
+    fn rejects_a_flipped_animation_of_another_size() {
+        let json = json!({
+            "forward_animation": animation_json("abcd"),
+            "flipped_animation": animation_json("a"),
+        });
+        match Asset::from_json(&json) {
+            Err(ByofishError::LengthMismatch(pointer)) => assert_eq!(pointer, "/flipped_animation"),
+            _ => panic!("expected a length mismatch"),
+        }
+    }
+
+    #[test]
+    fn resizing_stops_before_either_animation_is_empty() {
+        let forward = blank_animation(Size {
+            width: 4,
+            height: 1,
+        });
+        let flipped = blank_animation(Size {
+            width: 1,
+            height: 1,
+        });
+        let mut asset = Asset::from_animations(forward, flipped, false);
+        asset.resize(&Direction::Right, -2);
+        assert_eq!(asset.forward_animation[0][0].len(), 4);
+        assert_eq!(asset.flipped_animation[0][0].len(), 1);
+    }
+
+    #[test]
+    fn edits_that_change_nothing_are_not_recorded() {
+        let mut asset = Asset::from_animations(
+            blank_animation(Size {
+                width: 2,
+                height: 1,
+            }),
+            blank_animation(Size {
+                width: 2,
+                height: 1,
+            }),
+            false,
+        );
+        asset.handle_command(&command::Command::Paste);
+        asset.handle_command(&command::Command::SwapFrames);
+        asset.handle_command(&command::Command::MoveFrame(1));
+        asset.handle_command(&command::Command::Backspace);
+        assert!(!asset.is_dirty());
+
+        asset.handle_command(&command::Command::SetChar('a'));
+        asset.handle_command(&command::Command::Paste);
+        asset.handle_command(&command::Command::Undo);
+        assert_eq!(asset.forward_animation[0][0][0], EMPTY_COLOR_GLYPH);
+    }
+
+    #[test]
+    fn new_lines_split_typing_into_undo_steps() {
+        let mut asset = Asset::from_animations(
+            blank_animation(Size {
+                width: 2,
+                height: 2,
+            }),
+            blank_animation(Size {
+                width: 2,
+                height: 2,
+            }),
+            false,
+        );
+        asset.handle_command(&command::Command::TypeChar('a'));
+        asset.handle_command(&command::Command::NewLine);
+        asset.handle_command(&command::Command::TypeChar('c'));
+        asset.handle_command(&command::Command::Undo);
+        assert_eq!(asset.forward_animation[0][0][0].glyph, 'a');
+        assert_eq!(asset.forward_animation[0][1][0].glyph, ' ');
+    }
+
+    #[test]
+    fn trims_when_the_flipped_animation_is_still_blank() {
+        let mut asset = Asset::new_blank(Size {
+            width: 5,
+            height: 3,
+        });
+        asset.cursor_position = Position { x: 2, y: 1 };
+        asset.handle_command(&command::Command::SetChar('x'));
+        asset.handle_command(&command::Command::Trim);
+        assert!(
+            asset.get_size()
+                == Size {
+                    width: 1,
+                    height: 1
+                }
+        );
+        assert!(frame_size(&asset.flipped_animation) == asset.get_size());
+        assert_eq!(asset.forward_animation[0][0][0].glyph, 'x');
+    }
+
+    #[test]
+    fn rotating_turns_the_frame_half_way_round() {
+        let json = json!({
             "forward_animation": {
-                "symbols": forward_animation_symbols,
-                "colors": forward_animation_colors,
-                "highlights": forward_animation_highlights,
+                "symbols": [["<o", "^ "], ["<o", "^ "]],
+                "colors": [["  ", "  "], ["  ", "  "]],
+                "highlights": [["  ", "  "], ["  ", "  "]],
             },
-            "flipped_animation": {
-                "symbols": flipped_animation_symbols,
-                "colors": flipped_animation_colors,
-                "highlights": flipped_animation_highlights,
+        });
+        let mut asset = Asset::from_json(&json).unwrap();
+        asset.handle_command(&command::Command::Rotate(false));
+        let glyphs = |frame: &Vec<Vec<ColorGlyph>>| -> Vec<String> {
+            return frame
+                .iter()
+                .map(|line| line.iter().map(|color_glyph| color_glyph.glyph).collect())
+                .collect();
+        };
+        assert_eq!(glyphs(&asset.forward_animation[0]), vec![" v", "o>"]);
+        assert_eq!(glyphs(&asset.forward_animation[1]), vec!["<o", "^ "]);
+    }
+
+    #[test]
+    fn undo_and_redo_put_back_what_each_edit_touched() {
+        let mut asset = Asset::new_blank(Size {
+            width: 3,
+            height: 1,
+        });
+        let glyphs = |asset: &Asset| -> Vec<String> {
+            return asset
+                .forward_animation
+                .iter()
+                .map(|frame| {
+                    frame[0]
+                        .iter()
+                        .map(|color_glyph| color_glyph.glyph)
+                        .collect()
+                })
+                .collect();
+        };
+        asset.handle_command(&command::Command::SetChar('a'));
+        asset.handle_command(&command::Command::AddFrame);
+        asset.handle_command(&command::Command::SetChar('b'));
+        asset.handle_command(&command::Command::Trim);
+        assert_eq!(glyphs(&asset), vec!["b", "a"]);
+
+        asset.handle_command(&command::Command::Undo);
+        assert_eq!(glyphs(&asset), vec!["b  ", "a  "]);
+        asset.handle_command(&command::Command::Undo);
+        assert_eq!(glyphs(&asset), vec!["   ", "a  "]);
+        asset.handle_command(&command::Command::Undo);
+        assert_eq!(glyphs(&asset), vec!["a  "]);
+
+        asset.handle_command(&command::Command::Redo);
+        asset.handle_command(&command::Command::Redo);
+        asset.handle_command(&command::Command::Redo);
+        assert_eq!(glyphs(&asset), vec!["b", "a"]);
+        assert!(frame_size(&asset.flipped_animation) == asset.get_size());
+    }
+
+    // where the mark ends up for a mark on each frame of a four frame
+    // animation, so marks before, at and after the changed frame are covered
+    fn marks_after<F: Fn(&mut Asset)>(current_frame: usize, change: F) -> Vec<usize> {
+        let mut marks = Vec::new();
+        for mark in 0..4 {
+            let mut asset = Asset::from_animations(
+                vec![
+                    blank_animation(Size {
+                        width: 1,
+                        height: 1
+                    })[0]
+                        .clone();
+                    4
+                ],
+                blank_animation(Size {
+                    width: 1,
+                    height: 1,
+                }),
+                false,
+            );
+            asset.current_frame = current_frame;
+            asset.frame_mark = Some(mark);
+            change(&mut asset);
+            marks.push(asset.get_frame_mark().unwrap());
+        }
+        return marks;
+    }
+
+    #[test]
+    fn the_mark_follows_its_frame_when_a_frame_is_added() {
+        // the new frame goes in at the current frame
+        assert_eq!(marks_after(1, |asset| asset.add_frame()), vec![0, 2, 3, 4]);
+    }
+
+    #[test]
+    fn the_mark_follows_its_frame_when_a_frame_is_duplicated() {
+        // the copy goes after the current frame, the mark stays on the original
+        assert_eq!(
+            marks_after(1, |asset| asset.duplicate_frame()),
+            vec![0, 1, 3, 4]
+        );
+    }
+
+    #[test]
+    fn the_mark_follows_its_frame_when_a_frame_is_moved() {
+        assert_eq!(
+            marks_after(1, |asset| asset.move_frame(2)),
+            vec![0, 3, 1, 2]
+        );
+        assert_eq!(
+            marks_after(2, |asset| asset.move_frame(-2)),
+            vec![1, 2, 0, 3]
+        );
+    }
+
+    #[test]
+    fn frames_without_a_duration_are_saved_without_one() {
+        let json = json!({
+            "forward_animation": {
+                "symbols": [["a"], ["b"]],
+                "colors": [[" "], [" "]],
+                "highlights": [[" "], [" "]],
+                "durations": [100, null],
             },
         });
+        let mut asset = Asset::from_json(&json).unwrap();
+        asset.set_default_duration(40);
+        let exported = asset.export();
+        assert_eq!(
+            exported["forward_animation"]["durations"],
+            json!([100, null])
+        );
+        assert_eq!(
+            exported["flipped_animation"]["durations"],
+            json!([100, null])
+        );
     }
 }
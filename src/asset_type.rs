@@ -0,0 +1,59 @@
+use error::ByofishError;
+use serde_json::json;
+
+// what freefish does with an asset, and the settings that go with it
+pub struct FishSettings {}
+
+pub struct DuckSettings {
+    pub buoyancy: usize,
+}
+
+pub struct CrabSettings {}
+
+pub enum AssetType {
+    Fish(FishSettings),
+    Duck(DuckSettings),
+    Crab(CrabSettings),
+}
+
+impl AssetType {
+    pub fn from_name(name: &str) -> Option<AssetType> {
+        match name {
+            "fish" => return Some(AssetType::Fish(FishSettings {})),
+            "duck" => return Some(AssetType::Duck(DuckSettings { buoyancy: 0 })),
+            "crab" => return Some(AssetType::Crab(CrabSettings {})),
+            _ => return None,
+        }
+    }
+
+    // only ducks have anything in the file that says what they are, fish and
+    // crabs look the same so those come back as None
+    pub fn from_json(json: &serde_json::Value) -> Result<Option<AssetType>, ByofishError> {
+        match json.pointer("/buoyancy") {
+            Some(buoyancy) => match buoyancy.as_u64() {
+                Some(buoyancy) => {
+                    return Ok(Some(AssetType::Duck(DuckSettings {
+                        buoyancy: buoyancy as usize,
+                    })))
+                }
+                None => {
+                    return Err(ByofishError::WrongType(
+                        String::from("/buoyancy"),
+                        "a whole number",
+                    ))
+                }
+            },
+            None => return Ok(None),
+        }
+    }
+
+    // adds the type specific settings to an exported asset, and takes away
+    // the ones left over from a type it was saved as before
+    pub fn write_settings(&self, json: &mut serde_json::Value) {
+        if let AssetType::Duck(settings) = self {
+            json["buoyancy"] = json!(settings.buoyancy);
+        } else if let Some(object) = json.as_object_mut() {
+            object.remove("buoyancy");
+        }
+    }
+}
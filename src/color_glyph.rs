@@ -1,11 +1,29 @@
-use std::io::stdout;
-use terminal;
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Color {
+    Black,
+    DarkGrey,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    DarkRed,
+    DarkGreen,
+    DarkYellow,
+    DarkBlue,
+    DarkMagenta,
+    DarkCyan,
+    Grey,
+    Default,
+}
 
 #[derive(Clone, Copy, PartialEq, Debug)]
 pub struct ColorGlyph {
     pub glyph: char,
-    pub foreground_color: Option<terminal::Color>,
-    pub background_color: Option<terminal::Color>,
+    pub foreground_color: Option<Color>,
+    pub background_color: Option<Color>,
 }
 
 pub const EMPTY_COLOR_GLYPH: ColorGlyph = ColorGlyph {
@@ -14,41 +32,51 @@ pub const EMPTY_COLOR_GLYPH: ColorGlyph = ColorGlyph {
     background_color: None,
 };
 
-impl ColorGlyph {
-    pub fn print(&self) {
-        terminal::set_foreground_color(terminal::Color::Default);
-        terminal::set_background_color(terminal::Color::Default);
-        if let Some(color) = self.foreground_color {
-            terminal::set_foreground_color(color);
-        }
-        if let Some(color) = self.background_color {
-            terminal::set_background_color(color);
-        }
-        print!("{}", self.glyph);
+pub fn color_to_char(color: &Option<Color>) -> char {
+    match color {
+        Some(Color::DarkGrey) => 'a',
+        Some(Color::Red) => 'r',
+        Some(Color::Green) => 'g',
+        Some(Color::Yellow) => 'y',
+        Some(Color::Blue) => 'b',
+        Some(Color::Magenta) => 'm',
+        Some(Color::Cyan) => 'c',
+        Some(Color::White) => 'w',
+
+        Some(Color::Black) => 'A',
+        Some(Color::DarkRed) => 'R',
+        Some(Color::DarkGreen) => 'G',
+        Some(Color::DarkYellow) => 'Y',
+        Some(Color::DarkBlue) => 'B',
+        Some(Color::DarkMagenta) => 'M',
+        Some(Color::DarkCyan) => 'C',
+        Some(Color::Grey) => 'W',
+
+        // could do a little error checking here
+        _ => ' ',
     }
 }
 
-pub fn color_to_char(color: &Option<terminal::Color>) -> char {
+pub fn match_color(color: char) -> Option<Color> {
     match color {
-        Some(terminal::Color::DarkGrey) => 'a',
-        Some(terminal::Color::Red) => 'r',
-        Some(terminal::Color::Green) => 'g',
-        Some(terminal::Color::Yellow) => 'y',
-        Some(terminal::Color::Blue) => 'b',
-        Some(terminal::Color::Magenta) => 'm',
-        Some(terminal::Color::Cyan) => 'c',
-        Some(terminal::Color::White) => 'w',
-
-        Some(terminal::Color::Black) => 'A',
-        Some(terminal::Color::DarkRed) => 'R',
-        Some(terminal::Color::DarkGreen) => 'G',
-        Some(terminal::Color::DarkYellow) => 'Y',
-        Some(terminal::Color::DarkBlue) => 'B',
-        Some(terminal::Color::DarkMagenta) => 'M',
-        Some(terminal::Color::DarkCyan) => 'C',
-        Some(terminal::Color::Grey) => 'W',
+        'a' => return Some(Color::DarkGrey),
+        'r' => return Some(Color::Red),
+        'g' => return Some(Color::Green),
+        'y' => return Some(Color::Yellow),
+        'b' => return Some(Color::Blue),
+        'm' => return Some(Color::Magenta),
+        'c' => return Some(Color::Cyan),
+        'w' => return Some(Color::White),
 
-        // could do a little error checking here
-        _ => ' ',
+        'A' => return Some(Color::Black),
+        'R' => return Some(Color::DarkRed),
+        'G' => return Some(Color::DarkGreen),
+        'Y' => return Some(Color::DarkYellow),
+        'B' => return Some(Color::DarkBlue),
+        'M' => return Some(Color::DarkMagenta),
+        'C' => return Some(Color::DarkCyan),
+        'W' => return Some(Color::Grey),
+
+        _ => return None,
     }
 }
@@ -1 +1,7 @@
-
+#[derive(PartialEq, Copy, Clone)]
+pub enum Direction {
+    Left,
+    Right,
+    Up,
+    Down,
+}
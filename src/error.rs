@@ -1,12 +1,51 @@
-extern crate crossterm;
-
-pub fn error(msg: &str, code: i32) {
-    crossterm::style::SetForegroundColor(crossterm::style::Color::Red);
-    crossterm::style::SetBackgroundColor(crossterm::style::Color::Reset);
-    crossterm::style::SetAttribute(crossterm::style::Attribute::Bold);
-    print!("error:");
-    crossterm::style::SetForegroundColor(crossterm::style::Color::Reset);
-    crossterm::style::SetAttribute(crossterm::style::Attribute::Reset);
-    println!("{}", msg);
-    std::process::exit(code);
+use std::fmt;
+
+#[derive(Debug)]
+pub enum ByofishError {
+    // reading or writing a file failed
+    Io(std::io::Error),
+    // the file is not json at all
+    Json(serde_json::Error),
+    // the remaining variants hold the json pointer of the offending value
+    MissingKey(String),
+    WrongType(String, &'static str),
+    LengthMismatch(String),
+    // a glyph that doesn't take up exactly one cell
+    WideGlyph(String, char),
+    // a line of an input script that doesn't name a key
+    UnknownKey(usize, String),
+}
+
+impl fmt::Display for ByofishError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ByofishError::Io(e) => write!(f, "{}", e),
+            ByofishError::Json(e) => write!(f, "not proper json ({})", e),
+            ByofishError::MissingKey(pointer) => write!(f, "{} key is missing", pointer),
+            ByofishError::WrongType(pointer, expected) => {
+                write!(f, "{} is not {}", pointer, expected)
+            }
+            ByofishError::LengthMismatch(pointer) => write!(f, "{} differs in length", pointer),
+            ByofishError::UnknownKey(line, name) => {
+                write!(f, "line {}: {} is not a key", line, name)
+            }
+            ByofishError::WideGlyph(pointer, glyph) => {
+                write!(f, "{} has {:?} which is not one cell wide", pointer, glyph)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ByofishError {}
+
+impl From<std::io::Error> for ByofishError {
+    fn from(e: std::io::Error) -> ByofishError {
+        return ByofishError::Io(e);
+    }
+}
+
+impl From<serde_json::Error> for ByofishError {
+    fn from(e: serde_json::Error) -> ByofishError {
+        return ByofishError::Json(e);
+    }
 }
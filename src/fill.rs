@@ -0,0 +1,159 @@
+use animation::Position;
+use color_glyph::ColorGlyph;
+
+// what a cell has to share with the starting cell to be filled
+#[derive(Clone, Copy, PartialEq)]
+pub enum FillMatch {
+    Glyph,
+    Color,
+    Both,
+}
+
+impl FillMatch {
+    pub fn next(self) -> FillMatch {
+        match self {
+            FillMatch::Glyph => return FillMatch::Color,
+            FillMatch::Color => return FillMatch::Both,
+            FillMatch::Both => return FillMatch::Glyph,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            FillMatch::Glyph => return "glyph",
+            FillMatch::Color => return "color",
+            FillMatch::Both => return "both",
+        }
+    }
+
+    fn matches(self, a: &ColorGlyph, b: &ColorGlyph) -> bool {
+        match self {
+            FillMatch::Glyph => return a.glyph == b.glyph,
+            FillMatch::Color => return a.foreground_color == b.foreground_color,
+            FillMatch::Both => {
+                return a.glyph == b.glyph && a.foreground_color == b.foreground_color;
+            }
+        }
+    }
+}
+
+// which part of the cells a fill or stroke changes
+#[derive(Clone, Copy, PartialEq)]
+pub enum Layer {
+    Glyph,
+    Foreground,
+    Background,
+}
+
+// every cell connected to the start through up, down, left or right
+// neighbours that match the start cell
+pub fn fill_region(
+    frame: &[Vec<ColorGlyph>],
+    start: Position,
+    fill_match: FillMatch,
+) -> Vec<Position> {
+    let height = frame.len();
+    let width = frame[0].len();
+    let target = frame[start.y][start.x];
+    let mut visited = vec![vec![false; width]; height];
+    let mut region: Vec<Position> = Vec::new();
+    let mut to_visit: Vec<Position> = vec![start];
+
+    while let Some(pos) = to_visit.pop() {
+        if visited[pos.y][pos.x] || !fill_match.matches(&frame[pos.y][pos.x], &target) {
+            continue;
+        }
+        visited[pos.y][pos.x] = true;
+        region.push(pos);
+        if pos.x >= 1 {
+            to_visit.push(Position {
+                x: pos.x - 1,
+                y: pos.y,
+            });
+        }
+        if pos.x + 1 < width {
+            to_visit.push(Position {
+                x: pos.x + 1,
+                y: pos.y,
+            });
+        }
+        if pos.y >= 1 {
+            to_visit.push(Position {
+                x: pos.x,
+                y: pos.y - 1,
+            });
+        }
+        if pos.y + 1 < height {
+            to_visit.push(Position {
+                x: pos.x,
+                y: pos.y + 1,
+            });
+        }
+    }
+    return region;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use color_glyph::match_color;
+
+    // one string of glyphs and one of color letters per line
+    fn frame(glyphs: &[&str], colors: &[&str]) -> Vec<Vec<ColorGlyph>> {
+        return glyphs
+            .iter()
+            .zip(colors.iter())
+            .map(|(glyphs, colors)| {
+                glyphs
+                    .chars()
+                    .zip(colors.chars())
+                    .map(|(glyph, color)| ColorGlyph {
+                        glyph,
+                        foreground_color: match_color(color),
+                        background_color: None,
+                    })
+                    .collect()
+            })
+            .collect();
+    }
+
+    fn filled(region: Vec<Position>) -> Vec<(usize, usize)> {
+        let mut cells: Vec<(usize, usize)> = region.iter().map(|pos| (pos.x, pos.y)).collect();
+        cells.sort();
+        return cells;
+    }
+
+    #[test]
+    fn does_not_leak_through_diagonals() {
+        let frame = frame(&["ab", "ba"], &["  ", "  "]);
+        let region = fill_region(&frame, Position { x: 0, y: 0 }, FillMatch::Glyph);
+        assert_eq!(filled(region), vec![(0, 0)]);
+    }
+
+    #[test]
+    fn stops_at_a_wall_of_other_glyphs() {
+        let frame = frame(&[".#.", ".#.", ".#."], &["   ", "   ", "   "]);
+        let region = fill_region(&frame, Position { x: 0, y: 1 }, FillMatch::Glyph);
+        assert_eq!(filled(region), vec![(0, 0), (0, 1), (0, 2)]);
+        let region = fill_region(&frame, Position { x: 1, y: 0 }, FillMatch::Glyph);
+        assert_eq!(filled(region), vec![(1, 0), (1, 1), (1, 2)]);
+    }
+
+    #[test]
+    fn matches_by_glyph_color_or_both() {
+        let frame = frame(&["aab"], &["rgg"]);
+        let start = Position { x: 1, y: 0 };
+        assert_eq!(
+            filled(fill_region(&frame, start, FillMatch::Glyph)),
+            vec![(0, 0), (1, 0)]
+        );
+        assert_eq!(
+            filled(fill_region(&frame, start, FillMatch::Color)),
+            vec![(1, 0), (2, 0)]
+        );
+        assert_eq!(
+            filled(fill_region(&frame, start, FillMatch::Both)),
+            vec![(1, 0)]
+        );
+    }
+}
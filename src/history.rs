@@ -0,0 +1,128 @@
+use animation::{Animation, Durations, Frame, Position};
+use asset::Facing;
+
+// how many edits can be undone before the oldest are dropped
+const HISTORY_LIMIT: usize = 500;
+
+// which part of the asset an edit can change, only that part is kept
+#[derive(Clone, Copy, PartialEq)]
+pub enum Scope {
+    // one frame of one animation
+    Frame(Facing, usize),
+    // every frame and duration of one animation
+    Animation(Facing),
+    // both animations, for edits that change the size
+    Both,
+}
+
+// the part of the asset named by a scope, as it was at some point
+#[derive(Clone, PartialEq)]
+pub enum Saved {
+    Frame(Facing, usize, Frame),
+    Animation(Facing, Animation, Durations),
+    // forward then flipped
+    Both(Animation, Durations, Animation, Durations),
+}
+
+impl Saved {
+    pub fn scope(&self) -> Scope {
+        match self {
+            Saved::Frame(facing, frame_idx, _) => return Scope::Frame(*facing, *frame_idx),
+            Saved::Animation(facing, _, _) => return Scope::Animation(*facing),
+            Saved::Both(..) => return Scope::Both,
+        }
+    }
+}
+
+// what an edit changed and where the editor was, so undoing is just
+// swapping it back in
+#[derive(Clone)]
+pub struct Snapshot {
+    pub saved: Saved,
+    pub facing: Facing,
+    pub cursor_position: Position,
+    pub current_frame: usize,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum EditKind {
+    // runs of these are undone together
+    Glyph,
+    Color,
+    Highlight,
+    // everything else is undone one at a time
+    Other,
+}
+
+pub struct History {
+    undo_stack: Vec<Snapshot>,
+    redo_stack: Vec<Snapshot>,
+    // kind and scope of the previous edit if nothing has interrupted the run
+    last_edit: Option<(EditKind, Scope)>,
+}
+
+impl Default for History {
+    fn default() -> History {
+        return History::new();
+    }
+}
+
+impl History {
+    pub fn new() -> History {
+        return History {
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            last_edit: None,
+        };
+    }
+
+    // call with the state from before the edit is applied, a run only goes
+    // on while the edits stay in the same place
+    pub fn record(&mut self, before: Snapshot, kind: EditKind) {
+        self.redo_stack.clear();
+        let edit = (kind, before.saved.scope());
+        if kind != EditKind::Other && self.last_edit == Some(edit) {
+            return;
+        }
+        self.undo_stack.push(before);
+        if self.undo_stack.len() > HISTORY_LIMIT {
+            self.undo_stack.remove(0);
+        }
+        self.last_edit = Some(edit);
+    }
+
+    // the next edit starts a new undo step even if it is the same kind
+    pub fn break_run(&mut self) {
+        self.last_edit = None;
+    }
+
+    // the part of the asset the next undo or redo puts back, so the current
+    // state of that part can be handed in
+    pub fn undo_scope(&self) -> Option<Scope> {
+        return self
+            .undo_stack
+            .last()
+            .map(|snapshot| snapshot.saved.scope());
+    }
+
+    pub fn redo_scope(&self) -> Option<Scope> {
+        return self
+            .redo_stack
+            .last()
+            .map(|snapshot| snapshot.saved.scope());
+    }
+
+    pub fn undo(&mut self, current: Snapshot) -> Option<Snapshot> {
+        self.last_edit = None;
+        let previous = self.undo_stack.pop()?;
+        self.redo_stack.push(current);
+        return Some(previous);
+    }
+
+    pub fn redo(&mut self, current: Snapshot) -> Option<Snapshot> {
+        self.last_edit = None;
+        let next = self.redo_stack.pop()?;
+        self.undo_stack.push(current);
+        return Some(next);
+    }
+}
@@ -0,0 +1,26 @@
+#![allow(clippy::needless_return)]
+
+// the freefish asset model, for loading, editing and saving assets from the
+// byofish editor or any other tool, the editor's own terminal interface is
+// kept apart in tui
+
+extern crate crossterm;
+extern crate serde_json;
+
+pub mod animation;
+pub mod asset;
+pub mod asset_type;
+pub mod color_glyph;
+pub mod command;
+pub mod direction;
+pub mod error;
+pub mod fill;
+mod history;
+pub mod mirror;
+pub mod open_json;
+pub mod selection;
+pub mod shapes;
+pub mod transform;
+pub mod width;
+
+pub mod tui;
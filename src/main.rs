@@ -1,22 +1,12 @@
+#![allow(clippy::needless_return)]
+
+extern crate byofish;
 extern crate structopt;
 use structopt::StructOpt;
 
-// get rid of this dep
-extern crate serde_json;
-
-mod animation;
-mod color_glyph;
-mod error;
-mod terminal;
-use error::error;
-mod asset;
-mod command;
-mod decorations;
-mod input;
-mod menu;
-mod mode;
-mod open_json;
-mod pad;
+use byofish::error::ByofishError;
+use byofish::tui::{editor, input, menu, onion_skin, playback, surface, terminal};
+use byofish::{animation, asset, asset_type, mirror, open_json};
 
 #[derive(Debug, structopt::StructOpt)]
 #[structopt(
@@ -27,6 +17,58 @@ mod pad;
 struct Opt {
     #[structopt(name = "FILE")]
     file_name: String,
+    /// Width of a new asset when FILE does not exist
+    #[structopt(long, default_value = "10")]
+    width: usize,
+    /// Height of a new asset when FILE does not exist
+    #[structopt(long, default_value = "4")]
+    height: usize,
+    /// Type the asset is saved as, unless FILE is a duck which is kept as one
+    #[structopt(
+        long = "type",
+        default_value = "fish",
+        possible_values = &["fish", "duck", "crab"]
+    )]
+    asset_type: String,
+    /// JSON object of extra glyph pairs used when generating the flipped animation
+    #[structopt(long, parse(from_os_str))]
+    mirror_table: Option<std::path::PathBuf>,
+    /// Frames per second of the playback preview for frames without their own duration
+    #[structopt(long, default_value = "4")]
+    fps: f64,
+    /// Number of times the playback preview loops before stopping
+    #[structopt(long)]
+    loops: Option<usize>,
+    /// File of key presses, one per line like "ctrl+z", played before reading the keyboard
+    #[structopt(long, parse(from_os_str))]
+    script: Option<std::path::PathBuf>,
+    /// Number of earlier frames shown by the onion skin
+    #[structopt(long, default_value = "1")]
+    onion_back: usize,
+    /// Number of later frames shown by the onion skin
+    #[structopt(long, default_value = "0")]
+    onion_forward: usize,
+}
+
+// puts the terminal back if the editor had it, then prints the message and exits
+fn error(msg: &str, code: i32) -> ! {
+    terminal::reset();
+    terminal::set_foreground_color(terminal::Color::Red);
+    terminal::set_background_color(terminal::Color::Default);
+    print!("error:");
+    terminal::set_foreground_color(terminal::Color::Default);
+    println!("{}", msg);
+    std::process::exit(code);
+}
+
+// the asset and, if the file says, the type it was saved as
+fn load_asset(
+    path: &std::path::PathBuf,
+) -> Result<(asset::Asset, Option<asset_type::AssetType>), ByofishError> {
+    let json = open_json::open_json(path)?;
+    let asset = asset::Asset::from_json(&json)?;
+    let asset_type = asset_type::AssetType::from_json(&json)?;
+    return Ok((asset, asset_type));
 }
 
 fn main() {
@@ -39,55 +81,73 @@ fn main() {
         file_name = dir.to_os_string().into_string().unwrap();
     }
 
-    let mut asset = asset::Asset::new(&asset_path, &file_name);
-    // TODO:look at exsisting file and make the type based on that or default to fish
-    let mut save_menu = menu::SaveMenu::new(menu::AssetType::Fish(menu::FishSettings {}));
+    let asset_type = match asset_type::AssetType::from_name(&args.asset_type) {
+        Some(asset_type) => asset_type,
+        None => error(&format!("unknown asset type {}", args.asset_type), 1),
+    };
+    let (mut asset, asset_type) = if asset_path.exists() {
+        match load_asset(&asset_path) {
+            Ok((asset, file_type)) => (asset, file_type.unwrap_or(asset_type)),
+            Err(e) => error(&format!("could not load {}: {}", file_name, e), 1),
+        }
+    } else {
+        if args.width == 0 || args.height == 0 {
+            error("new assets need a width and height of at least 1", 1);
+        }
+        let asset = asset::Asset::new_blank(animation::Size {
+            width: args.width,
+            height: args.height,
+        });
+        (asset, asset_type)
+    };
+    let save_menu = menu::SaveMenu::new(asset_type, &asset_path.to_string_lossy());
+
+    let mut mirror_table = mirror::MirrorTable::new();
+    if let Some(table_path) = args.mirror_table {
+        if let Err(e) = mirror_table.load(&table_path) {
+            error(
+                &format!("could not load {}: {}", table_path.display(), e),
+                1,
+            );
+        }
+    }
+    asset.set_mirror_table(mirror_table);
+
+    if !args.fps.is_finite() || args.fps <= 0.0 {
+        error("fps must be greater than 0", 1);
+    }
+    asset.set_default_duration(((1000.0 / args.fps).round() as u64).max(1));
+    let playback = playback::Playback::new(args.loops);
+    let onion_skin = onion_skin::OnionSkin::new(args.onion_back, args.onion_forward);
 
-    let mut mode = mode::EditorMode::Glyph;
-    terminal::init();
+    // presses from the script are played before the keyboard takes over
+    let mut script = input::ScriptedInput::new(Vec::new());
+    if let Some(script_path) = args.script {
+        script = match input::ScriptedInput::from_file(&script_path) {
+            Ok(script) => script,
+            Err(e) => error(
+                &format!("could not load {}: {}", script_path.display(), e),
+                1,
+            ),
+        };
+    }
+    let mut keyboard = input::TerminalInput::new();
 
-    let start_time = std::time::SystemTime::now();
+    let mut editor = editor::Editor::new(asset, save_menu, playback, onion_skin);
+    // the terminal is put back when this goes out of scope or on a panic
+    let _terminal_guard = terminal::TerminalGuard::new();
+    let mut surface = surface::TerminalSurface::new();
     loop {
-        terminal::home_cursor();
-        // if not save mode
-        if mode == mode::EditorMode::Save {
-            save_menu.print();
-            pad::to_end();
-            if !save_menu.handle_input() {
-                mode = mode::EditorMode::Glyph;
-            }
+        if editor.needs_draw() {
+            editor.draw(&mut surface);
+        }
+        let source: &mut dyn input::InputSource = if script.is_empty() {
+            &mut keyboard
         } else {
-            decorations::print_frame_indicator(asset.get_frame_idx(), asset.get_frame_num());
-            let odd_sec: bool = (start_time.elapsed().unwrap().as_secs() % 2) == 1;
-            asset.print(odd_sec);
-            decorations::print_color_guide();
-            // else print save mode screen
-            if mode == mode::EditorMode::Glyph {
-                pad::print_line("\rmode:glyph");
-            } else {
-                pad::print_line("\rmode:color");
-            }
-            pad::to_end();
-
-            if let Some(cmd) = command::handle_input(&mode) {
-                match cmd {
-                    command::Command::Quit => break,
-                    command::Command::CycleMode => {
-                        if mode == mode::EditorMode::Glyph {
-                            mode = mode::EditorMode::Color;
-                        } else if mode == mode::EditorMode::Color {
-                            mode = mode::EditorMode::Glyph;
-                        }
-                    }
-                    command::Command::SaveMode => {
-                        mode = mode::EditorMode::Save;
-                    }
-                    _ => asset.handle_command(&cmd),
-                }
-            }
+            &mut script
+        };
+        if !editor.update(source) {
+            break;
         }
     }
-    // return terminal to regular state
-    terminal::reset();
-    std::process::exit(0);
 }
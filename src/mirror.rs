@@ -0,0 +1,243 @@
+use animation::Animation;
+use color_glyph::ColorGlyph;
+use error::ByofishError;
+use open_json::open_json;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use width::is_single_width;
+
+// glyphs that turn into each other when the art is flipped left to right
+const DEFAULT_PAIRS: [(char, char); 21] = [
+    ('<', '>'),
+    ('(', ')'),
+    ('/', '\\'),
+    ('[', ']'),
+    ('{', '}'),
+    ('d', 'b'),
+    ('p', 'q'),
+    ('«', '»'),
+    ('‹', '›'),
+    ('⟨', '⟩'),
+    ('◀', '▶'),
+    ('◁', '▷'),
+    ('┌', '┐'),
+    ('└', '┘'),
+    ('┏', '┓'),
+    ('┗', '┛'),
+    ('╭', '╮'),
+    ('╰', '╯'),
+    ('├', '┤'),
+    ('┣', '┫'),
+    ('▌', '▐'),
+];
+
+// glyphs that turn into each other when the art is flipped upside down
+const DEFAULT_VERTICAL_PAIRS: [(char, char); 16] = [
+    ('/', '\\'),
+    ('^', 'v'),
+    ('\'', ','),
+    ('b', 'p'),
+    ('d', 'q'),
+    ('▲', '▼'),
+    ('△', '▽'),
+    ('┌', '└'),
+    ('┐', '┘'),
+    ('┏', '┗'),
+    ('┓', '┛'),
+    ('╭', '╰'),
+    ('╮', '╯'),
+    ('┬', '┴'),
+    ('┳', '┻'),
+    ('▀', '▄'),
+];
+
+pub struct MirrorTable {
+    pairs: HashMap<char, char>,
+    vertical_pairs: HashMap<char, char>,
+}
+
+impl Default for MirrorTable {
+    fn default() -> MirrorTable {
+        return MirrorTable::new();
+    }
+}
+
+impl MirrorTable {
+    pub fn new() -> MirrorTable {
+        let mut table = MirrorTable {
+            pairs: HashMap::new(),
+            vertical_pairs: HashMap::new(),
+        };
+        for (left, right) in DEFAULT_PAIRS.iter() {
+            table.add_pair(*left, *right);
+        }
+        for (top, bottom) in DEFAULT_VERTICAL_PAIRS.iter() {
+            table.vertical_pairs.insert(*top, *bottom);
+            table.vertical_pairs.insert(*bottom, *top);
+        }
+        return table;
+    }
+
+    // both glyphs map to each other, and any pairs they were in before are
+    // broken up so mirroring twice always gives back the original
+    pub fn add_pair(&mut self, left: char, right: char) {
+        for glyph in [left, right].iter() {
+            if let Some(partner) = self.pairs.remove(glyph) {
+                self.pairs.remove(&partner);
+            }
+        }
+        self.pairs.insert(left, right);
+        self.pairs.insert(right, left);
+    }
+
+    // extends the table with a json object of pairs like { "<": ">" }
+    pub fn load(&mut self, path: &PathBuf) -> Result<(), ByofishError> {
+        let json = open_json(path)?;
+        let pairs = match json.as_object() {
+            Some(pairs) => pairs,
+            None => return Err(ByofishError::WrongType(String::from("(root)"), "an object")),
+        };
+        for (left, right) in pairs {
+            // keys are escaped since "/" is a glyph worth mirroring
+            let pointer = format!("/{}", left.replace('~', "~0").replace('/', "~1"));
+            let right = match right.as_str() {
+                Some(right) => right,
+                None => return Err(ByofishError::WrongType(pointer, "a string")),
+            };
+            let mut left_chars = left.chars();
+            let mut right_chars = right.chars();
+            match (
+                left_chars.next(),
+                left_chars.next(),
+                right_chars.next(),
+                right_chars.next(),
+            ) {
+                (Some(l), None, Some(r), None) => {
+                    for glyph in [l, r].iter() {
+                        if !is_single_width(*glyph) {
+                            return Err(ByofishError::WideGlyph(pointer, *glyph));
+                        }
+                    }
+                    self.add_pair(l, r);
+                }
+                _ => return Err(ByofishError::WrongType(pointer, "a pair of single glyphs")),
+            }
+        }
+        return Ok(());
+    }
+
+    pub fn mirror_glyph(&self, glyph: char) -> char {
+        return *self.pairs.get(&glyph).unwrap_or(&glyph);
+    }
+
+    pub fn flip_glyph(&self, glyph: char) -> char {
+        return *self.vertical_pairs.get(&glyph).unwrap_or(&glyph);
+    }
+
+    // reverses every line, colors and highlights move with their glyphs
+    // since they are stored together
+    pub fn mirror_frame(&self, frame: &mut [Vec<ColorGlyph>]) {
+        for line in frame.iter_mut() {
+            line.reverse();
+            for color_glyph in line.iter_mut() {
+                color_glyph.glyph = self.mirror_glyph(color_glyph.glyph);
+            }
+        }
+    }
+
+    // turns the frame upside down
+    pub fn flip_frame(&self, frame: &mut [Vec<ColorGlyph>]) {
+        frame.reverse();
+        for line in frame.iter_mut() {
+            for color_glyph in line.iter_mut() {
+                color_glyph.glyph = self.flip_glyph(color_glyph.glyph);
+            }
+        }
+    }
+
+    pub fn mirror_animation(&self, animation: &Animation) -> Animation {
+        let mut mirrored = animation.clone();
+        for frame in mirrored.iter_mut() {
+            self.mirror_frame(frame);
+        }
+        return mirrored;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use color_glyph::Color;
+    use color_glyph::EMPTY_COLOR_GLYPH;
+
+    fn load_table(name: &str, contents: &str) -> Result<MirrorTable, ByofishError> {
+        let path = std::env::temp_dir().join(format!("byofish_{}_{}", std::process::id(), name));
+        std::fs::write(&path, contents).unwrap();
+        let mut table = MirrorTable::new();
+        let result = table.load(&path);
+        std::fs::remove_file(&path).unwrap();
+        return result.map(|()| table);
+    }
+
+    fn line(glyphs: &str) -> Vec<ColorGlyph> {
+        return glyphs
+            .chars()
+            .map(|glyph| ColorGlyph {
+                glyph,
+                ..EMPTY_COLOR_GLYPH
+            })
+            .collect();
+    }
+
+    fn text(frame: &[Vec<ColorGlyph>]) -> Vec<String> {
+        return frame
+            .iter()
+            .map(|line| line.iter().map(|color_glyph| color_glyph.glyph).collect())
+            .collect();
+    }
+
+    #[test]
+    fn loads_pairs_both_ways() {
+        let table = load_table("pairs.json", r#"{ "/": "|", "+": "x" }"#).unwrap();
+        assert_eq!(table.mirror_glyph('/'), '|');
+        assert_eq!(table.mirror_glyph('|'), '/');
+        assert_eq!(table.mirror_glyph('x'), '+');
+    }
+
+    #[test]
+    fn overriding_a_pair_leaves_its_old_partner_on_its_own() {
+        let table = load_table("override.json", r#"{ "/": "|" }"#).unwrap();
+        assert_eq!(table.mirror_glyph('\\'), '\\');
+        for glyph in ['/', '|', '\\', '<', '>'].iter() {
+            assert_eq!(table.mirror_glyph(table.mirror_glyph(*glyph)), *glyph);
+        }
+    }
+
+    #[test]
+    fn errors_point_at_escaped_keys() {
+        let error = load_table("slash.json", r#"{ "a/b": "x" }"#).err().unwrap();
+        assert_eq!(error.to_string(), "/a~1b is not a pair of single glyphs");
+        let error = load_table("tilde.json", r#"{ "~": 1 }"#).err().unwrap();
+        assert_eq!(error.to_string(), "/~0 is not a string");
+        let error = load_table("array.json", r#"["<", ">"]"#).err().unwrap();
+        assert_eq!(error.to_string(), "(root) is not an object");
+    }
+
+    #[test]
+    fn mirroring_moves_colors_with_their_glyphs() {
+        let table = MirrorTable::new();
+        let mut frame = vec![line("<o)"), line("ab ")];
+        frame[0][0].foreground_color = Some(Color::Red);
+        table.mirror_frame(&mut frame);
+        assert_eq!(text(&frame), vec!["(o>", " da"]);
+        assert_eq!(frame[0][2].foreground_color, Some(Color::Red));
+    }
+
+    #[test]
+    fn flipping_turns_lines_and_glyphs_upside_down() {
+        let table = MirrorTable::new();
+        let mut frame = vec![line("/^\\"), line("|_|")];
+        table.flip_frame(&mut frame);
+        assert_eq!(text(&frame), vec!["|_|", "\\v/"]);
+    }
+}
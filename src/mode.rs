@@ -1,6 +0,0 @@
-#[derive(PartialEq)]
-pub enum EditorMode {
-    Glyph,
-    Color,
-    Save,
-}
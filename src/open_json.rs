@@ -1,20 +1,21 @@
-use error;
+use error::ByofishError;
 use std::fs::File;
+use std::io::Write;
 use std::path::PathBuf;
 
-pub fn open_json(path: &PathBuf, name: &str, asset_type: &str) -> serde_json::Value {
-    match File::open(path) {
-        Ok(f) => match serde_json::from_reader(f) {
-            Ok(j) => return j,
-            Err(_e) => error(
-                &format!("{} file {} is not proper json", asset_type, name),
-                1,
-            )
-            .into(),
-        },
-        Err(_e) => error(&format!("could not open {} file {}", asset_type, name), 1).into(),
-    }
+pub fn open_json(path: &PathBuf) -> Result<serde_json::Value, ByofishError> {
+    let file = File::open(path)?;
+    let json = serde_json::from_reader(file)?;
+    return Ok(json);
+}
+
+pub fn save_json(path: &PathBuf, json: &serde_json::Value) -> Result<(), ByofishError> {
+    let mut file = File::create(path)?;
+    file.write_all(format_json(json).as_bytes())?;
+    file.write_all(b"\n")?;
+    return Ok(());
 }
+
 pub fn format_json(json: &serde_json::Value) -> String {
     let json_string = serde_json::to_string(&json).unwrap();
     let json_chars = json_string.chars();
@@ -22,11 +23,17 @@ pub fn format_json(json: &serde_json::Value) -> String {
 
     let mut prev_c = ' ';
     let mut in_quotes = false;
+    // set after a backslash inside a string, so the next character is taken as is
+    let mut escaped = false;
     let mut depth: usize = 0;
 
     // this is not ideal but it does line things up
     for c in json_chars {
-        if c == '"' && prev_c != '\\' {
+        if in_quotes && escaped {
+            escaped = false;
+        } else if in_quotes && c == '\\' {
+            escaped = true;
+        } else if c == '"' {
             in_quotes = !in_quotes;
         }
 
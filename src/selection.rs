@@ -0,0 +1,38 @@
+use animation::{Position, Size};
+use color_glyph::ColorGlyph;
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct Rect {
+    pub position: Position,
+    pub size: Size,
+}
+
+impl Rect {
+    // the corners can be given in any order
+    pub fn from_corners(a: Position, b: Position) -> Rect {
+        return Rect {
+            position: Position {
+                x: a.x.min(b.x),
+                y: a.y.min(b.y),
+            },
+            size: Size {
+                width: a.x.max(b.x) - a.x.min(b.x) + 1,
+                height: a.y.max(b.y) - a.y.min(b.y) + 1,
+            },
+        };
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        return position.x >= self.position.x
+            && position.x < self.position.x + self.size.width
+            && position.y >= self.position.y
+            && position.y < self.position.y + self.size.height;
+    }
+}
+
+#[derive(Clone)]
+pub struct Clipboard {
+    pub cells: Vec<Vec<ColorGlyph>>,
+    // where the cells were copied from
+    pub origin: Position,
+}
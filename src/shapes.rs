@@ -0,0 +1,235 @@
+use animation::Position;
+use selection::Rect;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Shape {
+    Line,
+    Rectangle,
+    // a rectangle drawn with box drawing glyphs
+    Box,
+    Ellipse,
+}
+
+// cells along the line, with a glyph that follows the line through each cell
+pub fn line(start: Position, end: Position) -> Vec<(Position, char)> {
+    let (x0, y0) = (start.x as isize, start.y as isize);
+    let (x1, y1) = (end.x as isize, end.y as isize);
+    let dx = (x1 - x0).abs();
+    let dy = -(y1 - y0).abs();
+    let step_x = if x0 < x1 { 1 } else { -1 };
+    let step_y = if y0 < y1 { 1 } else { -1 };
+
+    // bresenham
+    let mut points: Vec<(isize, isize)> = Vec::new();
+    let (mut x, mut y) = (x0, y0);
+    let mut err = dx + dy;
+    loop {
+        points.push((x, y));
+        if x == x1 && y == y1 {
+            break;
+        }
+        let err2 = 2 * err;
+        if err2 >= dy {
+            err += dy;
+            x += step_x;
+        }
+        if err2 <= dx {
+            err += dx;
+            y += step_y;
+        }
+    }
+
+    let mut cells: Vec<(Position, char)> = Vec::new();
+    for idx in 0..points.len() {
+        // the step into the cell decides its glyph, the first cell uses the step out
+        let (from, to) = if idx == 0 {
+            (points[0], *points.get(1).unwrap_or(&points[0]))
+        } else {
+            (points[idx - 1], points[idx])
+        };
+        let glyph = match (to.0 - from.0, to.1 - from.1) {
+            (0, 0) => '-',
+            (_, 0) => '-',
+            (0, _) => '|',
+            (step_x, step_y) if step_x == step_y => '\\',
+            _ => '/',
+        };
+        let (x, y) = points[idx];
+        cells.push((
+            Position {
+                x: x as usize,
+                y: y as usize,
+            },
+            glyph,
+        ));
+    }
+    return cells;
+}
+
+// cells around the edge of the rectangle
+pub fn rectangle(rect: Rect) -> Vec<Position> {
+    let mut cells: Vec<Position> = Vec::new();
+    for y in rect.position.y..rect.position.y + rect.size.height {
+        for x in rect.position.x..rect.position.x + rect.size.width {
+            let on_edge = y == rect.position.y
+                || y == rect.position.y + rect.size.height - 1
+                || x == rect.position.x
+                || x == rect.position.x + rect.size.width - 1;
+            if on_edge {
+                cells.push(Position { x, y });
+            }
+        }
+    }
+    return cells;
+}
+
+// the edge of the rectangle with the box drawing glyph for each cell
+pub fn box_border(rect: Rect) -> Vec<(Position, char)> {
+    let top = rect.position.y;
+    let bottom = rect.position.y + rect.size.height - 1;
+    let left = rect.position.x;
+    let right = rect.position.x + rect.size.width - 1;
+
+    let mut cells: Vec<(Position, char)> = Vec::new();
+    for pos in rectangle(rect) {
+        let glyph = if top == bottom {
+            '─'
+        } else if left == right {
+            '│'
+        } else {
+            match (pos.x, pos.y) {
+                (x, y) if x == left && y == top => '┌',
+                (x, y) if x == right && y == top => '┐',
+                (x, y) if x == left && y == bottom => '└',
+                (x, y) if x == right && y == bottom => '┘',
+                (_, y) if y == top || y == bottom => '─',
+                _ => '│',
+            }
+        };
+        cells.push((pos, glyph));
+    }
+    return cells;
+}
+
+// cells on the outline of the ellipse that fits in the rectangle
+pub fn ellipse(rect: Rect) -> Vec<Position> {
+    let radius_x = rect.size.width as f64 / 2.0;
+    let radius_y = rect.size.height as f64 / 2.0;
+    let center_x = rect.position.x as f64 + radius_x;
+    let center_y = rect.position.y as f64 + radius_y;
+    // measured from the middle of the cell
+    let inside = |x: isize, y: isize| -> bool {
+        let offset_x = (x as f64 + 0.5 - center_x) / radius_x;
+        let offset_y = (y as f64 + 0.5 - center_y) / radius_y;
+        return offset_x * offset_x + offset_y * offset_y <= 1.0;
+    };
+
+    let mut cells: Vec<Position> = Vec::new();
+    for y in rect.position.y..rect.position.y + rect.size.height {
+        for x in rect.position.x..rect.position.x + rect.size.width {
+            let (x_i, y_i) = (x as isize, y as isize);
+            if !inside(x_i, y_i) {
+                continue;
+            }
+            // on the outline when a neighbour is outside
+            let on_edge = !inside(x_i - 1, y_i)
+                || !inside(x_i + 1, y_i)
+                || !inside(x_i, y_i - 1)
+                || !inside(x_i, y_i + 1);
+            if on_edge {
+                cells.push(Position { x, y });
+            }
+        }
+    }
+    return cells;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use animation::Size;
+
+    fn pos(x: usize, y: usize) -> Position {
+        return Position { x, y };
+    }
+
+    fn cells(cells: Vec<(Position, char)>) -> Vec<(usize, usize, char)> {
+        return cells
+            .iter()
+            .map(|(pos, glyph)| (pos.x, pos.y, *glyph))
+            .collect();
+    }
+
+    fn rect(width: usize, height: usize) -> Rect {
+        return Rect {
+            position: pos(0, 0),
+            size: Size { width, height },
+        };
+    }
+
+    #[test]
+    fn lines_follow_their_direction() {
+        assert_eq!(
+            cells(line(pos(0, 0), pos(2, 2))),
+            vec![(0, 0, '\\'), (1, 1, '\\'), (2, 2, '\\')]
+        );
+        assert_eq!(
+            cells(line(pos(2, 2), pos(0, 0))),
+            vec![(2, 2, '\\'), (1, 1, '\\'), (0, 0, '\\')]
+        );
+        assert_eq!(
+            cells(line(pos(0, 2), pos(2, 0))),
+            vec![(0, 2, '/'), (1, 1, '/'), (2, 0, '/')]
+        );
+        assert_eq!(
+            cells(line(pos(2, 0), pos(0, 2))),
+            vec![(2, 0, '/'), (1, 1, '/'), (0, 2, '/')]
+        );
+    }
+
+    #[test]
+    fn shallow_and_steep_lines_mix_glyphs() {
+        assert_eq!(
+            cells(line(pos(0, 0), pos(3, 1))),
+            vec![(0, 0, '-'), (1, 0, '-'), (2, 1, '\\'), (3, 1, '-')]
+        );
+        assert_eq!(
+            cells(line(pos(0, 0), pos(1, 3))),
+            vec![(0, 0, '|'), (0, 1, '|'), (1, 2, '\\'), (1, 3, '|')]
+        );
+        assert_eq!(cells(line(pos(1, 1), pos(1, 1))), vec![(1, 1, '-')]);
+    }
+
+    #[test]
+    fn thin_boxes_are_single_lines() {
+        assert_eq!(
+            cells(box_border(rect(1, 3))),
+            vec![(0, 0, '│'), (0, 1, '│'), (0, 2, '│')]
+        );
+        assert_eq!(
+            cells(box_border(rect(3, 1))),
+            vec![(0, 0, '─'), (1, 0, '─'), (2, 0, '─')]
+        );
+        assert_eq!(
+            cells(box_border(rect(2, 2))),
+            vec![(0, 0, '┌'), (1, 0, '┐'), (0, 1, '└'), (1, 1, '┘')]
+        );
+    }
+
+    #[test]
+    fn thin_ellipses_fill_their_rectangle() {
+        let column: Vec<(usize, usize)> = ellipse(rect(1, 3)).iter().map(|p| (p.x, p.y)).collect();
+        assert_eq!(column, vec![(0, 0), (0, 1), (0, 2)]);
+        let row: Vec<(usize, usize)> = ellipse(rect(3, 1)).iter().map(|p| (p.x, p.y)).collect();
+        assert_eq!(row, vec![(0, 0), (1, 0), (2, 0)]);
+    }
+
+    #[test]
+    fn ellipses_leave_their_middle_empty() {
+        let outline: Vec<(usize, usize)> = ellipse(rect(5, 5)).iter().map(|p| (p.x, p.y)).collect();
+        assert!(!outline.contains(&(2, 2)));
+        assert!(outline.contains(&(2, 0)));
+        assert!(outline.contains(&(0, 2)));
+        assert!(!outline.contains(&(0, 0)));
+    }
+}
@@ -0,0 +1,76 @@
+use animation::Animation;
+use color_glyph::{ColorGlyph, EMPTY_COLOR_GLYPH};
+use direction::Direction;
+
+// how many empty lines or columns every frame has on each side
+#[derive(Clone, Copy)]
+pub struct Border {
+    pub top: usize,
+    pub bottom: usize,
+    pub left: usize,
+    pub right: usize,
+}
+
+// moves everything in the frame one cell over, what falls off the edge
+// comes back on the other side when wrapping and is lost otherwise
+pub fn shift_frame(frame: &mut Vec<Vec<ColorGlyph>>, direction: &Direction, wrap: bool) {
+    match direction {
+        Direction::Up => {
+            let line = frame.remove(0);
+            if wrap {
+                frame.push(line);
+            } else {
+                frame.push(vec![EMPTY_COLOR_GLYPH; line.len()]);
+            }
+        }
+        Direction::Down => {
+            let line = frame.pop().unwrap();
+            if wrap {
+                frame.insert(0, line);
+            } else {
+                frame.insert(0, vec![EMPTY_COLOR_GLYPH; line.len()]);
+            }
+        }
+        Direction::Left => {
+            for line in frame.iter_mut() {
+                let color_glyph = line.remove(0);
+                line.push(if wrap { color_glyph } else { EMPTY_COLOR_GLYPH });
+            }
+        }
+        Direction::Right => {
+            for line in frame.iter_mut() {
+                let color_glyph = line.pop().unwrap();
+                line.insert(0, if wrap { color_glyph } else { EMPTY_COLOR_GLYPH });
+            }
+        }
+    }
+}
+
+// the empty edges shared by every frame, None when there is nothing drawn at all
+pub fn empty_border(animation: &Animation) -> Option<Border> {
+    let height = animation[0].len();
+    let width = animation[0][0].len();
+    let mut used_rows = vec![false; height];
+    let mut used_columns = vec![false; width];
+    for frame in animation {
+        for (y, line) in frame.iter().enumerate() {
+            for (x, color_glyph) in line.iter().enumerate() {
+                if *color_glyph != EMPTY_COLOR_GLYPH {
+                    used_rows[y] = true;
+                    used_columns[x] = true;
+                }
+            }
+        }
+    }
+
+    let top = used_rows.iter().position(|used| *used)?;
+    let bottom = used_rows.iter().rev().position(|used| *used)?;
+    let left = used_columns.iter().position(|used| *used)?;
+    let right = used_columns.iter().rev().position(|used| *used)?;
+    return Some(Border {
+        top,
+        bottom,
+        left,
+        right,
+    });
+}
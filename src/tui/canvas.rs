@@ -0,0 +1,116 @@
+use animation::Position;
+use asset::Asset;
+use color_glyph::ColorGlyph;
+use selection::Rect;
+use tui::onion_skin::OnionSkin;
+use tui::surface::Surface;
+use tui::terminal;
+
+pub fn print_color_glyph(surface: &mut dyn Surface, color_glyph: &ColorGlyph) {
+    surface.set_foreground_color(
+        color_glyph
+            .foreground_color
+            .unwrap_or(terminal::Color::Default),
+    );
+    surface.set_background_color(
+        color_glyph
+            .background_color
+            .unwrap_or(terminal::Color::Default),
+    );
+    surface.print(&color_glyph.glyph.to_string());
+}
+
+// the current frame of the asset in a border
+pub fn print_asset(
+    surface: &mut dyn Surface,
+    asset: &Asset,
+    show_cursor: bool,
+    onion_skin: &OnionSkin,
+) {
+    let view = Rect {
+        position: Position { x: 0, y: 0 },
+        size: asset.get_size(),
+    };
+    print_asset_view(surface, asset, view, show_cursor, onion_skin);
+}
+
+// prints only the part of the frame inside the view, for windows too
+// small to show all of it
+pub fn print_asset_view(
+    surface: &mut dyn Surface,
+    asset: &Asset,
+    view: Rect,
+    show_cursor: bool,
+    onion_skin: &OnionSkin,
+) {
+    let animation = asset.get_animation(asset.get_facing());
+    let frame_idx = asset.get_frame_idx();
+    let selection = asset.get_selection();
+    let first_line = view.position.y;
+    let last_line = view.position.y + view.size.height - 1;
+    let first_glyph = view.position.x;
+    let last_glyph = view.position.x + view.size.width - 1;
+    for line_idx in first_line..=last_line {
+        // print top line
+        if line_idx == first_line {
+            surface.set_foreground_color(terminal::Color::Default);
+            surface.set_background_color(terminal::Color::Default);
+            surface.print(&format!("┏{}┓ \r\n", "━".repeat(view.size.width)));
+        }
+        for glyph_idx in first_glyph..=last_glyph {
+            if glyph_idx == first_glyph {
+                surface.set_foreground_color(terminal::Color::Default);
+                surface.set_background_color(terminal::Color::Default);
+                surface.print("┃");
+            }
+            let pos = Position {
+                x: glyph_idx,
+                y: line_idx,
+            };
+            // TODO make cursor flashing
+            if pos == asset.get_cursor_position() && show_cursor {
+                let cursor = ColorGlyph {
+                    glyph: 'X',
+                    foreground_color: None,
+                    background_color: None,
+                };
+                print_color_glyph(surface, &cursor);
+            } else {
+                let mut color_glyph = animation[frame_idx][line_idx][glyph_idx];
+                let selected = match selection {
+                    Some(rect) => rect.contains(pos),
+                    None => false,
+                };
+                if selected && color_glyph.background_color.is_none() {
+                    color_glyph.background_color = Some(terminal::Color::DarkGrey);
+                }
+                let ghost = if color_glyph.glyph == ' ' {
+                    onion_skin.ghost(animation, frame_idx, pos)
+                } else {
+                    None
+                };
+                if let Some(glyph) = ghost {
+                    // dimmed so it can't be mistaken for this frame's art
+                    let ghost = ColorGlyph {
+                        glyph,
+                        foreground_color: Some(terminal::Color::DarkGrey),
+                        background_color: color_glyph.background_color,
+                    };
+                    print_color_glyph(surface, &ghost);
+                } else {
+                    print_color_glyph(surface, &color_glyph);
+                }
+            }
+            if glyph_idx == last_glyph {
+                surface.set_foreground_color(terminal::Color::Default);
+                surface.set_background_color(terminal::Color::Default);
+                surface.print("┃ ");
+            }
+        }
+        surface.print("\r\n");
+        // print bottom line
+        if line_idx == last_line {
+            surface.print(&format!("┗{}┛ \r\n", "━".repeat(view.size.width)));
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use color_glyph::{color_to_char, ColorGlyph};
+use tui::canvas::print_color_glyph;
+use tui::pad;
+use tui::surface::Surface;
+use tui::terminal::Color;
+pub fn print_frame_indicator(
+    surface: &mut dyn Surface,
+    frame_idx: usize,
+    frame_num: usize,
+    frame_mark: Option<usize>,
+) {
+    let mut indicators: Vec<String> = Vec::new();
+    for idx in 0..frame_num {
+        if idx == frame_idx {
+            indicators.push(format!("{}[*]", idx))
+        } else if Some(idx) == frame_mark {
+            indicators.push(format!("{}[m]", idx))
+        } else {
+            indicators.push(format!("{}[ ]", idx))
+        }
+    }
+    let width = surface.size().0;
+    pad::print_line(surface, &fit_indicators(&indicators, frame_idx, width));
+}
+
+// when they don't all fit only the ones around the current frame are kept,
+// with < and > showing there are more
+fn fit_indicators(indicators: &[String], frame_idx: usize, width: usize) -> String {
+    let all_len: usize = indicators.iter().map(|indicator| indicator.len()).sum();
+    if all_len <= width {
+        return indicators.concat();
+    }
+    // room for the < and >
+    let mut len = indicators[frame_idx].len() + 2;
+    let (mut first, mut last) = (frame_idx, frame_idx);
+    loop {
+        let mut grew = false;
+        if last + 1 < indicators.len() && len + indicators[last + 1].len() <= width {
+            last += 1;
+            len += indicators[last].len();
+            grew = true;
+        }
+        if first > 0 && len + indicators[first - 1].len() <= width {
+            first -= 1;
+            len += indicators[first].len();
+            grew = true;
+        }
+        if !grew {
+            break;
+        }
+    }
+    let before = if first > 0 { "<" } else { "" };
+    let after = if last + 1 < indicators.len() { ">" } else { "" };
+    return format!("{}{}{}", before, indicators[first..=last].concat(), after);
+}
+
+pub fn print_color_guide(surface: &mut dyn Surface) {
+    surface.print("\r");
+    surface.set_foreground_color(Color::Black);
+
+    surface.set_background_color(Color::DarkGrey);
+    surface.print("a");
+    surface.set_background_color(Color::Red);
+    surface.print("r");
+    surface.set_background_color(Color::Green);
+    surface.print("g");
+    surface.set_background_color(Color::Yellow);
+    surface.print("y");
+    surface.set_background_color(Color::Blue);
+    surface.print("b");
+    surface.set_background_color(Color::Magenta);
+    surface.print("m");
+    surface.set_background_color(Color::Cyan);
+    surface.print("c");
+    surface.set_background_color(Color::White);
+    surface.print("w");
+
+    pad::new_line(surface);
+    surface.set_foreground_color(Color::White);
+
+    surface.set_background_color(Color::Black);
+    surface.print("a");
+    surface.set_background_color(Color::DarkRed);
+    surface.print("r");
+    surface.set_background_color(Color::DarkGreen);
+    surface.print("g");
+    surface.set_background_color(Color::DarkYellow);
+    surface.print("y");
+    surface.set_background_color(Color::DarkBlue);
+    surface.print("b");
+    surface.set_background_color(Color::DarkMagenta);
+    surface.print("m");
+    surface.set_background_color(Color::DarkCyan);
+    surface.print("c");
+    surface.set_background_color(Color::Grey);
+    surface.print("w");
+
+    surface.set_foreground_color(Color::Default);
+    surface.set_background_color(Color::Default);
+
+    pad::new_line(surface);
+}
+
+pub fn print_brush(surface: &mut dyn Surface, brush: &ColorGlyph) {
+    surface.print("\rbrush:[");
+    print_color_glyph(surface, brush);
+    surface.set_foreground_color(Color::Default);
+    surface.set_background_color(Color::Default);
+    // the same letters the file format uses, - for no color
+    let color_name = |color: &Option<Color>| match color {
+        Some(_) => color_to_char(color),
+        None => '-',
+    };
+    pad::print_line(
+        surface,
+        &format!(
+            "] color:{} highlight:{}",
+            color_name(&brush.foreground_color),
+            color_name(&brush.background_color)
+        ),
+    );
+}
@@ -0,0 +1,272 @@
+use asset::{Asset, Facing};
+use command::Command;
+use std::path::PathBuf;
+use std::time::SystemTime;
+use tui::canvas::print_asset_view;
+use tui::decorations;
+use tui::input::{InputSource, Press};
+use tui::keymap::handle_input;
+use tui::layout::layout;
+use tui::menu::{MenuAction, SaveMenu};
+use tui::mode::EditorMode;
+use tui::onion_skin::OnionSkin;
+use tui::pad;
+use tui::playback::Playback;
+use tui::surface::Surface;
+
+// everything the editor keeps between key presses
+pub struct Editor {
+    asset: Asset,
+    save_menu: SaveMenu,
+    playback: Playback,
+    onion_skin: OnionSkin,
+    mode: EditorMode,
+    // shown under the mode line, e.g. the result of a save
+    status: String,
+    // set after the first Esc when there are unsaved changes
+    quit_pending: bool,
+    // the cursor blinks every second from here
+    start_time: SystemTime,
+    // set when something on screen may have changed since the last draw
+    redraw: bool,
+    // whether the cursor was showing in the last draw
+    drawn_blink: bool,
+    // the terminal's new width and height, applied on the next draw
+    resize: Option<(usize, usize)>,
+}
+
+// passes presses through and remembers if there were any
+struct WatchedInput<'a> {
+    source: &'a mut dyn InputSource,
+    pressed: bool,
+}
+
+impl<'a> InputSource for WatchedInput<'a> {
+    fn next_press(&mut self) -> Option<Press> {
+        let press = self.source.next_press();
+        if press.is_some() {
+            self.pressed = true;
+        }
+        return press;
+    }
+
+    fn take_resize(&mut self) -> Option<(usize, usize)> {
+        return self.source.take_resize();
+    }
+}
+
+impl Editor {
+    pub fn new(
+        asset: Asset,
+        save_menu: SaveMenu,
+        playback: Playback,
+        onion_skin: OnionSkin,
+    ) -> Editor {
+        return Editor {
+            asset,
+            save_menu,
+            playback,
+            onion_skin,
+            mode: EditorMode::Glyph,
+            status: String::new(),
+            quit_pending: false,
+            start_time: SystemTime::now(),
+            redraw: true,
+            drawn_blink: false,
+            resize: None,
+        };
+    }
+
+    pub fn get_asset(&self) -> &Asset {
+        return &self.asset;
+    }
+
+    pub fn get_mode(&self) -> &EditorMode {
+        return &self.mode;
+    }
+
+    pub fn get_status(&self) -> &str {
+        return &self.status;
+    }
+
+    fn blink(&self) -> bool {
+        match self.start_time.elapsed() {
+            Ok(elapsed) => return elapsed.as_secs() % 2 == 1,
+            Err(_) => return false,
+        }
+    }
+
+    // nothing needs drawing until a press, a playback step or the cursor blinking
+    pub fn needs_draw(&self) -> bool {
+        return self.redraw || self.blink() != self.drawn_blink;
+    }
+
+    pub fn draw(&mut self, surface: &mut dyn Surface) {
+        self.redraw = false;
+        self.drawn_blink = self.blink();
+        if let Some((width, height)) = self.resize.take() {
+            surface.resize(width, height);
+        }
+        // nothing from the last draw is kept, what is left over from a
+        // bigger canvas or the save menu would otherwise stay on screen
+        surface.clear();
+        if self.mode == EditorMode::Save {
+            self.save_menu.print(surface);
+        } else {
+            decorations::print_frame_indicator(
+                surface,
+                self.asset.get_frame_idx(),
+                self.asset.get_frame_num(),
+                self.asset.get_frame_mark(),
+            );
+            let layout = layout(
+                surface.size(),
+                self.asset.get_size(),
+                self.asset.get_cursor_position(),
+            );
+            print_asset_view(
+                surface,
+                &self.asset,
+                layout.view,
+                self.drawn_blink,
+                &self.onion_skin,
+            );
+            if layout.show_guide {
+                decorations::print_color_guide(surface);
+            }
+            decorations::print_brush(surface, &self.asset.get_brush());
+            pad::print_line(surface, &self.mode_line());
+        }
+        pad::print_line(surface, &format!("\r{}", self.status));
+        pad::to_end(surface);
+        surface.flush();
+    }
+
+    fn mode_line(&self) -> String {
+        let mode_name = match self.mode {
+            EditorMode::Glyph => "glyph",
+            EditorMode::Text => "text",
+            EditorMode::Color => "color",
+            EditorMode::Highlight => "highlight",
+            EditorMode::Save => "save",
+        };
+        let facing_name = match self.asset.get_facing() {
+            Facing::Forward => "forward",
+            Facing::Flipped => "flipped",
+        };
+        // toggles that are on get listed after the mode
+        let mut tags = String::new();
+        let toggles = [
+            (self.asset.is_dirty(), "modified"),
+            (self.playback.is_playing(), "playing"),
+            (self.onion_skin.is_enabled(), "onion"),
+            (self.asset.get_selection().is_some(), "select"),
+            (self.asset.is_transparent_paste(), "transparent paste"),
+            (self.asset.is_text_wrap(), "wrap"),
+            (self.asset.is_shift_clip(), "clip shift"),
+        ];
+        for (on, tag) in toggles.iter() {
+            if *on {
+                tags.push_str(&format!(" [{}]", tag));
+            }
+        }
+        return format!(
+            "\rmode:{} animation:{} fill:{} duration:{}ms{}",
+            mode_name,
+            facing_name,
+            self.asset.get_fill_match().name(),
+            self.asset.get_frame_duration(),
+            tags
+        );
+    }
+
+    // handles at most one press, false once the editor should quit
+    pub fn update(&mut self, source: &mut dyn InputSource) -> bool {
+        let mut source = WatchedInput {
+            source,
+            pressed: false,
+        };
+        let running = self.handle_input(&mut source);
+        if source.pressed {
+            self.redraw = true;
+        }
+        if let Some(size) = source.take_resize() {
+            self.resize = Some(size);
+            self.redraw = true;
+        }
+        return running;
+    }
+
+    fn handle_input(&mut self, source: &mut dyn InputSource) -> bool {
+        if self.mode == EditorMode::Save {
+            self.update_save_menu(source);
+            return true;
+        }
+        if self.playback.update(&mut self.asset) {
+            self.redraw = true;
+        }
+
+        if let Some(cmd) = handle_input(&self.mode, source) {
+            if let Command::Quit = cmd {
+                if !self.asset.is_dirty() || self.quit_pending {
+                    return false;
+                }
+                self.quit_pending = true;
+                self.status = String::from("unsaved changes, press esc again to quit");
+                return true;
+            }
+            self.quit_pending = false;
+            self.status.clear();
+            match cmd {
+                Command::Quit => {}
+                Command::CycleMode => {
+                    if self.mode == EditorMode::Glyph {
+                        self.mode = EditorMode::Text;
+                    } else if self.mode == EditorMode::Text {
+                        self.mode = EditorMode::Color;
+                    } else if self.mode == EditorMode::Color {
+                        self.mode = EditorMode::Highlight;
+                    } else if self.mode == EditorMode::Highlight {
+                        self.mode = EditorMode::Glyph;
+                    }
+                }
+                Command::SaveMode => {
+                    self.mode = EditorMode::Save;
+                }
+                Command::TogglePlayback => {
+                    self.playback.toggle(&self.asset);
+                }
+                Command::ToggleOnionSkin => {
+                    self.onion_skin.toggle();
+                }
+                Command::GenerateFlipped => {
+                    self.asset.handle_command(&cmd);
+                    self.status = String::from("generated flipped animation");
+                }
+                _ => self.asset.handle_command(&cmd),
+            }
+        }
+        return true;
+    }
+
+    fn update_save_menu(&mut self, source: &mut dyn InputSource) {
+        match self.save_menu.handle_input(source) {
+            MenuAction::Stay => {}
+            MenuAction::Close => {
+                self.mode = EditorMode::Glyph;
+            }
+            MenuAction::Save => {
+                let save_path = PathBuf::from(self.save_menu.get_path());
+                match self.asset.save(&save_path, self.save_menu.get_asset_type()) {
+                    Ok(()) => {
+                        self.status = format!("saved {}", save_path.display());
+                        self.mode = EditorMode::Glyph;
+                    }
+                    Err(e) => {
+                        self.status = format!("could not save {}: {}", save_path.display(), e);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,352 @@
+extern crate crossterm;
+
+pub use direction::Direction;
+use error::ByofishError;
+use std::collections::VecDeque;
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(PartialEq)]
+pub enum Key {
+    Esc,
+    Tab,
+    Insert,
+    Delete,
+    Home,
+    End,
+    Enter,
+    Backspace,
+    PageUp,
+    PageDown,
+    Direction(Direction),
+    Glyph(char),
+}
+
+#[derive(PartialEq)]
+pub enum Modifier {
+    Control,
+    Shift,
+    Alt,
+    Super,
+}
+
+#[derive(PartialEq)]
+pub struct Press {
+    pub key: Key,
+    pub modifier: Option<Modifier>,
+}
+
+impl Press {
+    pub fn new(key: Key, modifier: Option<Modifier>) -> Press {
+        return Press { key, modifier };
+    }
+
+    // reads presses written like "a", "space", "ctrl+z" or "shift+left"
+    pub fn from_name(name: &str) -> Option<Press> {
+        let (modifier, key_name) = match name.find('+') {
+            // a lone "+" is the glyph, not a separator
+            Some(idx) if idx > 0 && idx + 1 < name.len() => {
+                let modifier = match &name[..idx] {
+                    "ctrl" => Modifier::Control,
+                    "shift" => Modifier::Shift,
+                    "alt" => Modifier::Alt,
+                    "super" => Modifier::Super,
+                    _ => return None,
+                };
+                (Some(modifier), &name[idx + 1..])
+            }
+            _ => (None, name),
+        };
+        let key = match key_name {
+            "esc" => Key::Esc,
+            "tab" => Key::Tab,
+            "insert" => Key::Insert,
+            "delete" => Key::Delete,
+            "home" => Key::Home,
+            "end" => Key::End,
+            "enter" => Key::Enter,
+            "backspace" => Key::Backspace,
+            "pageup" => Key::PageUp,
+            "pagedown" => Key::PageDown,
+            "left" => Key::Direction(Direction::Left),
+            "right" => Key::Direction(Direction::Right),
+            "up" => Key::Direction(Direction::Up),
+            "down" => Key::Direction(Direction::Down),
+            "space" => Key::Glyph(' '),
+            _ => {
+                let mut chars = key_name.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(glyph), None) => Key::Glyph(glyph),
+                    _ => return None,
+                }
+            }
+        };
+        return Some(Press::new(key, modifier));
+    }
+}
+
+// where key presses come from, None when nothing has been pressed
+pub trait InputSource {
+    fn next_press(&mut self) -> Option<Press>;
+
+    // the newest size the terminal was resized to since the last call
+    fn take_resize(&mut self) -> Option<(usize, usize)> {
+        return None;
+    }
+}
+
+// the keyboard, through crossterm
+pub struct TerminalInput {
+    resize: Option<(usize, usize)>,
+}
+
+impl Default for TerminalInput {
+    fn default() -> TerminalInput {
+        return TerminalInput::new();
+    }
+}
+
+impl TerminalInput {
+    pub fn new() -> TerminalInput {
+        return TerminalInput { resize: None };
+    }
+}
+
+impl InputSource for TerminalInput {
+    fn next_press(&mut self) -> Option<Press> {
+        match get_event() {
+            Some(Event::Press(press)) => return Some(press),
+            Some(Event::Resize(width, height)) => {
+                self.resize = Some((width, height));
+                return None;
+            }
+            None => return None,
+        }
+    }
+
+    fn take_resize(&mut self) -> Option<(usize, usize)> {
+        return self.resize.take();
+    }
+}
+
+// presses given ahead of time, for tests or replaying a session
+pub struct ScriptedInput {
+    presses: VecDeque<Press>,
+}
+
+impl ScriptedInput {
+    pub fn new(presses: Vec<Press>) -> ScriptedInput {
+        return ScriptedInput {
+            presses: presses.into_iter().collect(),
+        };
+    }
+
+    // one press per line, blank lines and lines starting with # are skipped
+    pub fn from_file(path: &PathBuf) -> Result<ScriptedInput, ByofishError> {
+        let script = fs::read_to_string(path)?;
+        let mut presses: Vec<Press> = Vec::new();
+        for (line_idx, line) in script.lines().enumerate() {
+            // trimming would eat a press of the space glyph, so only cut the line ending
+            let line = line.trim_end_matches('\r');
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match Press::from_name(line) {
+                Some(press) => presses.push(press),
+                None => {
+                    return Err(ByofishError::UnknownKey(line_idx + 1, String::from(line)));
+                }
+            }
+        }
+        return Ok(ScriptedInput::new(presses));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.presses.is_empty();
+    }
+}
+
+impl InputSource for ScriptedInput {
+    fn next_press(&mut self) -> Option<Press> {
+        return self.presses.pop_front();
+    }
+}
+
+pub enum Event {
+    Press(Press),
+    // the terminal's new width and height
+    Resize(usize, usize),
+}
+
+pub fn get_event() -> Option<Event> {
+    loop {
+        if !crossterm::event::poll(std::time::Duration::from_millis(10)).unwrap() {
+            return None;
+        }
+        // gauranteed to be Some
+        match crossterm::event::read().unwrap() {
+            crossterm::event::Event::Key(key_event) => {
+                if let Some(press) = from_crossterm_key(key_event) {
+                    return Some(Event::Press(press));
+                }
+            }
+            crossterm::event::Event::Resize(width, height) => {
+                return Some(Event::Resize(width as usize, height as usize));
+            }
+            _ => (),
+        }
+    }
+}
+
+fn from_crossterm_key(key_event: crossterm::event::KeyEvent) -> Option<Press> {
+    if let crossterm::event::KeyCode::Char(c) = key_event.code {
+        return Some(Press::new(
+            Key::Glyph(c),
+            from_crossterm_modifier(key_event.modifiers),
+        ));
+    }
+    match &key_event {
+        crossterm::event::KeyEvent {
+            code: crossterm::event::KeyCode::Left,
+            ..
+        } => {
+            return Some(Press::new(
+                Key::Direction(Direction::Left),
+                from_crossterm_modifier(key_event.modifiers),
+            ));
+        }
+        crossterm::event::KeyEvent {
+            code: crossterm::event::KeyCode::Right,
+            ..
+        } => {
+            return Some(Press::new(
+                Key::Direction(Direction::Right),
+                from_crossterm_modifier(key_event.modifiers),
+            ));
+        }
+        crossterm::event::KeyEvent {
+            code: crossterm::event::KeyCode::Up,
+            ..
+        } => {
+            return Some(Press::new(
+                Key::Direction(Direction::Up),
+                from_crossterm_modifier(key_event.modifiers),
+            ));
+        }
+        crossterm::event::KeyEvent {
+            code: crossterm::event::KeyCode::Down,
+            ..
+        } => {
+            return Some(Press::new(
+                Key::Direction(Direction::Down),
+                from_crossterm_modifier(key_event.modifiers),
+            ));
+        }
+        crossterm::event::KeyEvent {
+            code: crossterm::event::KeyCode::Esc,
+            ..
+        } => {
+            return Some(Press::new(
+                Key::Esc,
+                from_crossterm_modifier(key_event.modifiers),
+            ));
+        }
+        crossterm::event::KeyEvent {
+            code: crossterm::event::KeyCode::Tab,
+            ..
+        } => {
+            return Some(Press::new(
+                Key::Tab,
+                from_crossterm_modifier(key_event.modifiers),
+            ));
+        }
+        crossterm::event::KeyEvent {
+            code: crossterm::event::KeyCode::Insert,
+            ..
+        } => {
+            return Some(Press::new(
+                Key::Insert,
+                from_crossterm_modifier(key_event.modifiers),
+            ));
+        }
+        crossterm::event::KeyEvent {
+            code: crossterm::event::KeyCode::Delete,
+            ..
+        } => {
+            return Some(Press::new(
+                Key::Delete,
+                from_crossterm_modifier(key_event.modifiers),
+            ));
+        }
+        crossterm::event::KeyEvent {
+            code: crossterm::event::KeyCode::Home,
+            ..
+        } => {
+            return Some(Press::new(
+                Key::Home,
+                from_crossterm_modifier(key_event.modifiers),
+            ));
+        }
+        crossterm::event::KeyEvent {
+            code: crossterm::event::KeyCode::End,
+            ..
+        } => {
+            return Some(Press::new(
+                Key::End,
+                from_crossterm_modifier(key_event.modifiers),
+            ));
+        }
+        crossterm::event::KeyEvent {
+            code: crossterm::event::KeyCode::PageUp,
+            ..
+        } => {
+            return Some(Press::new(
+                Key::PageUp,
+                from_crossterm_modifier(key_event.modifiers),
+            ));
+        }
+        crossterm::event::KeyEvent {
+            code: crossterm::event::KeyCode::PageDown,
+            ..
+        } => {
+            return Some(Press::new(
+                Key::PageDown,
+                from_crossterm_modifier(key_event.modifiers),
+            ));
+        }
+        crossterm::event::KeyEvent {
+            code: crossterm::event::KeyCode::Backspace,
+            ..
+        } => {
+            return Some(Press::new(
+                Key::Backspace,
+                from_crossterm_modifier(key_event.modifiers),
+            ));
+        }
+        crossterm::event::KeyEvent {
+            code: crossterm::event::KeyCode::Enter,
+            ..
+        } => {
+            return Some(Press::new(
+                Key::Enter,
+                from_crossterm_modifier(key_event.modifiers),
+            ));
+        }
+        _ => (),
+    }
+    return None;
+}
+
+fn from_crossterm_modifier(modifier: crossterm::event::KeyModifiers) -> Option<Modifier> {
+    match modifier {
+        crossterm::event::KeyModifiers::SHIFT => return Some(Modifier::Shift),
+        crossterm::event::KeyModifiers::CONTROL => return Some(Modifier::Control),
+        crossterm::event::KeyModifiers::ALT => return Some(Modifier::Alt),
+        crossterm::event::KeyModifiers::SUPER => return Some(Modifier::Super),
+        // shifted glyphs like 'H' already carry the shift
+        m if m == crossterm::event::KeyModifiers::ALT | crossterm::event::KeyModifiers::SHIFT => {
+            return Some(Modifier::Alt)
+        }
+        _ => return None,
+    }
+}
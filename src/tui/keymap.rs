@@ -0,0 +1,546 @@
+extern crate crossterm;
+use command::Command;
+use fill::Layer;
+use shapes::Shape;
+use tui::input;
+use tui::mode::EditorMode;
+use tui::terminal;
+use width::is_single_width;
+
+// which command, if any, each key press asks for in each mode
+pub fn handle_input(mode: &EditorMode, source: &mut dyn input::InputSource) -> Option<Command> {
+    if let Some(press) = source.next_press() {
+        if exit(&press) {
+            return Some(Command::Quit);
+        } else if cycle_mode(&press) {
+            return Some(Command::CycleMode);
+        } else if *mode == EditorMode::Text && new_line(&press) {
+            return Some(Command::NewLine);
+        } else if *mode == EditorMode::Text && backspace(&press) {
+            return Some(Command::Backspace);
+        } else if save_mode(&press) {
+            return Some(Command::SaveMode);
+        } else if let Some(direction) = move_cursor(&press) {
+            return Some(Command::MoveCursor(direction));
+        } else if let Some((direction, magnitude)) = resize(&press) {
+            return Some(Command::Resize(direction, magnitude));
+        } else if add_frame(&press) {
+            return Some(Command::AddFrame);
+        } else if delete_frame(&press) {
+            return Some(Command::DeleteFrame);
+        } else if duplicate_frame(&press) {
+            return Some(Command::DuplicateFrame);
+        } else if let Some(num) = move_frame(&press) {
+            return Some(Command::MoveFrame(num));
+        } else if swap_frames(&press) {
+            return Some(Command::SwapFrames);
+        } else if let Some(steps) = change_duration(&press) {
+            return Some(Command::ChangeDuration(steps));
+        } else if let Some(num) = cycle_frame(&press) {
+            return Some(Command::CycleFrame(num));
+        } else if let Some(all_frames) = flip_horizontal(&press) {
+            return Some(Command::FlipHorizontal(all_frames));
+        } else if let Some(all_frames) = flip_vertical(&press) {
+            return Some(Command::FlipVertical(all_frames));
+        } else if let Some(all_frames) = rotate(&press) {
+            return Some(Command::Rotate(all_frames));
+        } else if let Some(direction) = shift(&press) {
+            return Some(Command::Shift(direction));
+        } else if toggle_shift_clip(&press) {
+            return Some(Command::ToggleShiftClip);
+        } else if trim(&press) {
+            return Some(Command::Trim);
+        } else if switch_facing(&press) {
+            return Some(Command::SwitchFacing);
+        } else if generate_flipped(&press) {
+            return Some(Command::GenerateFlipped);
+        } else if undo(&press) {
+            return Some(Command::Undo);
+        } else if redo(&press) {
+            return Some(Command::Redo);
+        } else if toggle_playback(&press) {
+            return Some(Command::TogglePlayback);
+        } else if toggle_onion_skin(&press) {
+            return Some(Command::ToggleOnionSkin);
+        } else if toggle_selection(&press) {
+            return Some(Command::ToggleSelection);
+        } else if copy(&press) {
+            return Some(Command::Copy);
+        } else if cut(&press) {
+            return Some(Command::Cut);
+        } else if paste(&press) {
+            return Some(Command::Paste);
+        } else if toggle_transparent_paste(&press) {
+            return Some(Command::ToggleTransparentPaste);
+        } else if mark_frame(&press) {
+            return Some(Command::MarkFrame);
+        } else if apply_to_all_frames(&press) {
+            return Some(Command::ApplyToAllFrames);
+        } else if apply_to_frame_range(&press) {
+            return Some(Command::ApplyToFrameRange);
+        } else if fill(&press) {
+            match mode {
+                EditorMode::Color => return Some(Command::Fill(Layer::Foreground)),
+                EditorMode::Highlight => return Some(Command::Fill(Layer::Background)),
+                _ => return Some(Command::Fill(Layer::Glyph)),
+            }
+        } else if cycle_fill_match(&press) {
+            return Some(Command::CycleFillMatch);
+        } else if let Some(shape) = draw_shape(&press) {
+            return Some(Command::DrawShape(shape));
+        } else if pick_brush(&press) {
+            return Some(Command::PickBrush);
+        } else if stamp_brush(&press) {
+            return Some(Command::StampBrush);
+        } else if line_start(&press) {
+            return Some(Command::LineStart);
+        } else if line_end(&press) {
+            return Some(Command::LineEnd);
+        } else if toggle_text_wrap(&press) {
+            return Some(Command::ToggleTextWrap);
+        }
+
+        if *mode == EditorMode::Glyph {
+            if let Some(character) = set_glyph(&press) {
+                return Some(Command::SetChar(character));
+            }
+        } else if *mode == EditorMode::Text {
+            if let Some(character) = set_glyph(&press) {
+                return Some(Command::TypeChar(character));
+            }
+        } else if *mode == EditorMode::Color {
+            if let Some(color) = set_color(&press) {
+                return Some(Command::SetColor(Some(color)));
+            } else if clear_color(&press) {
+                return Some(Command::SetColor(None));
+            }
+        } else if *mode == EditorMode::Highlight {
+            if let Some(color) = set_color(&press) {
+                return Some(Command::SetHighlight(Some(color)));
+            } else if clear_color(&press) {
+                return Some(Command::SetHighlight(None));
+            }
+        }
+    }
+    return None;
+}
+
+fn exit(press: &input::Press) -> bool {
+    if press.key == input::Key::Esc {
+        return true;
+    }
+    return false;
+}
+
+fn cycle_mode(press: &input::Press) -> bool {
+    if press.key == input::Key::Tab {
+        return true;
+    }
+    return false;
+}
+
+fn save_mode(press: &input::Press) -> bool {
+    if press.key == input::Key::Enter {
+        return true;
+    }
+    return false;
+}
+
+fn move_cursor(press: &input::Press) -> Option<input::Direction> {
+    if let input::Key::Direction(d) = press.key {
+        if press.modifier.is_none() {
+            return Some(d);
+        }
+    }
+    return None;
+}
+
+fn resize(press: &input::Press) -> Option<(input::Direction, isize)> {
+    if let input::Key::Direction(d) = press.key {
+        if press.modifier == Some(input::Modifier::Control) {
+            return Some((d, 1));
+        }
+        if press.modifier == Some(input::Modifier::Shift) {
+            return Some((d, -1));
+        }
+    }
+    return None;
+}
+
+fn add_frame(press: &input::Press) -> bool {
+    if press.key == input::Key::Insert {
+        return true;
+    }
+    return false;
+}
+
+fn delete_frame(press: &input::Press) -> bool {
+    if press.key == input::Key::Delete {
+        return true;
+    }
+    return false;
+}
+
+fn duplicate_frame(press: &input::Press) -> bool {
+    if press.key == input::Key::Glyph('d') && press.modifier == Some(input::Modifier::Alt) {
+        return true;
+    }
+    return false;
+}
+
+fn move_frame(press: &input::Press) -> Option<isize> {
+    if press.modifier != Some(input::Modifier::Control) {
+        return None;
+    }
+    if press.key == input::Key::PageUp {
+        return Some(1);
+    } else if press.key == input::Key::PageDown {
+        return Some(-1);
+    }
+    return None;
+}
+
+fn swap_frames(press: &input::Press) -> bool {
+    if press.key == input::Key::Glyph('s') && press.modifier == Some(input::Modifier::Alt) {
+        return true;
+    }
+    return false;
+}
+
+fn change_duration(press: &input::Press) -> Option<isize> {
+    if press.modifier != Some(input::Modifier::Alt) {
+        return None;
+    }
+    match press.key {
+        input::Key::Glyph('=') | input::Key::Glyph('+') => return Some(1),
+        input::Key::Glyph('-') => return Some(-1),
+        _ => return None,
+    }
+}
+
+fn cycle_frame(press: &input::Press) -> Option<isize> {
+    if press.key == input::Key::PageUp {
+        return Some(1);
+    } else if press.key == input::Key::PageDown {
+        return Some(-1);
+    }
+    return None;
+}
+
+fn flip_horizontal(press: &input::Press) -> Option<bool> {
+    if press.modifier != Some(input::Modifier::Alt) {
+        return None;
+    }
+    match press.key {
+        input::Key::Glyph('h') => return Some(false),
+        input::Key::Glyph('H') => return Some(true),
+        _ => return None,
+    }
+}
+
+fn flip_vertical(press: &input::Press) -> Option<bool> {
+    if press.modifier != Some(input::Modifier::Alt) {
+        return None;
+    }
+    match press.key {
+        input::Key::Glyph('v') => return Some(false),
+        input::Key::Glyph('V') => return Some(true),
+        _ => return None,
+    }
+}
+
+fn rotate(press: &input::Press) -> Option<bool> {
+    if press.modifier != Some(input::Modifier::Alt) {
+        return None;
+    }
+    match press.key {
+        input::Key::Glyph('o') => return Some(false),
+        input::Key::Glyph('O') => return Some(true),
+        _ => return None,
+    }
+}
+
+fn shift(press: &input::Press) -> Option<input::Direction> {
+    if let input::Key::Direction(d) = press.key {
+        if press.modifier == Some(input::Modifier::Alt) {
+            return Some(d);
+        }
+    }
+    return None;
+}
+
+fn toggle_shift_clip(press: &input::Press) -> bool {
+    if press.key == input::Key::Glyph('c') && press.modifier == Some(input::Modifier::Alt) {
+        return true;
+    }
+    return false;
+}
+
+fn trim(press: &input::Press) -> bool {
+    if press.key == input::Key::Glyph('t') && press.modifier == Some(input::Modifier::Alt) {
+        return true;
+    }
+    return false;
+}
+
+fn switch_facing(press: &input::Press) -> bool {
+    if press.key == input::Key::Glyph('f') && press.modifier == Some(input::Modifier::Control) {
+        return true;
+    }
+    return false;
+}
+
+fn generate_flipped(press: &input::Press) -> bool {
+    if press.key == input::Key::Glyph('g') && press.modifier == Some(input::Modifier::Control) {
+        return true;
+    }
+    return false;
+}
+
+fn undo(press: &input::Press) -> bool {
+    if press.key == input::Key::Glyph('z') && press.modifier == Some(input::Modifier::Control) {
+        return true;
+    }
+    return false;
+}
+
+fn redo(press: &input::Press) -> bool {
+    if press.key == input::Key::Glyph('y') && press.modifier == Some(input::Modifier::Control) {
+        return true;
+    }
+    return false;
+}
+
+fn toggle_playback(press: &input::Press) -> bool {
+    if press.key == input::Key::Glyph('p') && press.modifier == Some(input::Modifier::Control) {
+        return true;
+    }
+    return false;
+}
+
+fn toggle_onion_skin(press: &input::Press) -> bool {
+    if press.key == input::Key::Glyph('o') && press.modifier == Some(input::Modifier::Control) {
+        return true;
+    }
+    return false;
+}
+
+fn toggle_selection(press: &input::Press) -> bool {
+    if press.key == input::Key::Glyph('b') && press.modifier == Some(input::Modifier::Control) {
+        return true;
+    }
+    return false;
+}
+
+fn copy(press: &input::Press) -> bool {
+    if press.key == input::Key::Glyph('c') && press.modifier == Some(input::Modifier::Control) {
+        return true;
+    }
+    return false;
+}
+
+fn cut(press: &input::Press) -> bool {
+    if press.key == input::Key::Glyph('x') && press.modifier == Some(input::Modifier::Control) {
+        return true;
+    }
+    return false;
+}
+
+fn paste(press: &input::Press) -> bool {
+    if press.key == input::Key::Glyph('v') && press.modifier == Some(input::Modifier::Control) {
+        return true;
+    }
+    return false;
+}
+
+fn toggle_transparent_paste(press: &input::Press) -> bool {
+    if press.key == input::Key::Glyph('t') && press.modifier == Some(input::Modifier::Control) {
+        return true;
+    }
+    return false;
+}
+
+fn mark_frame(press: &input::Press) -> bool {
+    if press.key == input::Key::Glyph('k') && press.modifier == Some(input::Modifier::Control) {
+        return true;
+    }
+    return false;
+}
+
+fn apply_to_all_frames(press: &input::Press) -> bool {
+    if press.key == input::Key::Glyph('a') && press.modifier == Some(input::Modifier::Control) {
+        return true;
+    }
+    return false;
+}
+
+fn apply_to_frame_range(press: &input::Press) -> bool {
+    if press.key == input::Key::Glyph('r') && press.modifier == Some(input::Modifier::Control) {
+        return true;
+    }
+    return false;
+}
+
+fn fill(press: &input::Press) -> bool {
+    if press.key == input::Key::Glyph('u') && press.modifier == Some(input::Modifier::Control) {
+        return true;
+    }
+    return false;
+}
+
+fn cycle_fill_match(press: &input::Press) -> bool {
+    if press.key == input::Key::Glyph('w') && press.modifier == Some(input::Modifier::Control) {
+        return true;
+    }
+    return false;
+}
+
+fn draw_shape(press: &input::Press) -> Option<Shape> {
+    if press.modifier != Some(input::Modifier::Alt) {
+        return None;
+    }
+    match press.key {
+        input::Key::Glyph('l') => return Some(Shape::Line),
+        input::Key::Glyph('r') => return Some(Shape::Rectangle),
+        input::Key::Glyph('b') => return Some(Shape::Box),
+        input::Key::Glyph('e') => return Some(Shape::Ellipse),
+        _ => return None,
+    }
+}
+
+fn pick_brush(press: &input::Press) -> bool {
+    if press.key == input::Key::Glyph('e') && press.modifier == Some(input::Modifier::Control) {
+        return true;
+    }
+    return false;
+}
+
+fn stamp_brush(press: &input::Press) -> bool {
+    if press.key == input::Key::Glyph('d') && press.modifier == Some(input::Modifier::Control) {
+        return true;
+    }
+    return false;
+}
+
+fn new_line(press: &input::Press) -> bool {
+    if press.key == input::Key::Enter {
+        return true;
+    }
+    return false;
+}
+
+fn backspace(press: &input::Press) -> bool {
+    if press.key == input::Key::Backspace {
+        return true;
+    }
+    return false;
+}
+
+fn line_start(press: &input::Press) -> bool {
+    if press.key == input::Key::Home {
+        return true;
+    }
+    return false;
+}
+
+fn line_end(press: &input::Press) -> bool {
+    if press.key == input::Key::End {
+        return true;
+    }
+    return false;
+}
+
+fn toggle_text_wrap(press: &input::Press) -> bool {
+    if press.key == input::Key::Glyph('w') && press.modifier == Some(input::Modifier::Alt) {
+        return true;
+    }
+    return false;
+}
+
+fn set_glyph(press: &input::Press) -> Option<char> {
+    if let input::Key::Glyph(g) = press.key {
+        if is_single_width(g) {
+            return Some(g);
+        }
+    }
+    return None;
+}
+
+fn clear_color(press: &input::Press) -> bool {
+    if press.key == input::Key::Glyph(' ') {
+        return true;
+    }
+    return false;
+}
+
+fn set_color(press: &input::Press) -> Option<terminal::Color> {
+    match *press {
+        // lighter colors
+        input::Press {
+            key: input::Key::Glyph('a'),
+            modifier: None,
+        } => return Some(terminal::Color::DarkGrey),
+        input::Press {
+            key: input::Key::Glyph('r'),
+            modifier: None,
+        } => return Some(terminal::Color::Red),
+        input::Press {
+            key: input::Key::Glyph('g'),
+            modifier: None,
+        } => return Some(terminal::Color::Green),
+        input::Press {
+            key: input::Key::Glyph('y'),
+            modifier: None,
+        } => return Some(terminal::Color::Yellow),
+        input::Press {
+            key: input::Key::Glyph('b'),
+            modifier: None,
+        } => return Some(terminal::Color::Blue),
+        input::Press {
+            key: input::Key::Glyph('m'),
+            modifier: None,
+        } => return Some(terminal::Color::Magenta),
+        input::Press {
+            key: input::Key::Glyph('c'),
+            modifier: None,
+        } => return Some(terminal::Color::Cyan),
+        input::Press {
+            key: input::Key::Glyph('w'),
+            modifier: None,
+        } => return Some(terminal::Color::White),
+        // darker colors
+        input::Press {
+            key: input::Key::Glyph('A'),
+            modifier: Some(input::Modifier::Shift),
+        } => return Some(terminal::Color::Black),
+        input::Press {
+            key: input::Key::Glyph('R'),
+            modifier: Some(input::Modifier::Shift),
+        } => return Some(terminal::Color::DarkRed),
+        input::Press {
+            key: input::Key::Glyph('G'),
+            modifier: Some(input::Modifier::Shift),
+        } => return Some(terminal::Color::DarkGreen),
+        input::Press {
+            key: input::Key::Glyph('Y'),
+            modifier: Some(input::Modifier::Shift),
+        } => return Some(terminal::Color::DarkYellow),
+        input::Press {
+            key: input::Key::Glyph('B'),
+            modifier: Some(input::Modifier::Shift),
+        } => return Some(terminal::Color::DarkBlue),
+        input::Press {
+            key: input::Key::Glyph('M'),
+            modifier: Some(input::Modifier::Shift),
+        } => return Some(terminal::Color::DarkMagenta),
+        input::Press {
+            key: input::Key::Glyph('C'),
+            modifier: Some(input::Modifier::Shift),
+        } => return Some(terminal::Color::DarkCyan),
+        input::Press {
+            key: input::Key::Glyph('W'),
+            modifier: Some(input::Modifier::Shift),
+        } => return Some(terminal::Color::Grey),
+        _ => return None,
+    };
+}
@@ -0,0 +1,47 @@
+use animation::{Position, Size};
+use selection::Rect;
+
+// the frame bar, the canvas border, and the brush, mode and status lines
+const FIXED_LINES: usize = 6;
+const GUIDE_LINES: usize = 2;
+// the canvas border on both sides and the space after it
+const BORDER_COLUMNS: usize = 3;
+
+pub struct Layout {
+    // the part of the frame that fits on screen
+    pub view: Rect,
+    pub show_guide: bool,
+}
+
+// fits the editor into a screen of the given width and height, the color
+// guide is the first thing dropped and then the canvas only shows the part
+// around the cursor
+pub fn layout(screen: (usize, usize), frame_size: Size, cursor: Position) -> Layout {
+    let (width, height) = screen;
+    let show_guide = height >= frame_size.height + FIXED_LINES + GUIDE_LINES;
+    let guide_lines = if show_guide { GUIDE_LINES } else { 0 };
+    let view_size = Size {
+        height: height
+            .saturating_sub(FIXED_LINES + guide_lines)
+            .clamp(1, frame_size.height),
+        width: width
+            .saturating_sub(BORDER_COLUMNS)
+            .clamp(1, frame_size.width),
+    };
+    return Layout {
+        view: Rect {
+            position: Position {
+                x: follow(cursor.x, view_size.width, frame_size.width),
+                y: follow(cursor.y, view_size.height, frame_size.height),
+            },
+            size: view_size,
+        },
+        show_guide,
+    };
+}
+
+// where a window into a line of the given length starts so the cursor
+// stays near its middle
+fn follow(cursor: usize, window: usize, length: usize) -> usize {
+    return cursor.saturating_sub(window / 2).min(length - window);
+}
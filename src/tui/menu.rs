@@ -0,0 +1,183 @@
+use asset_type::{AssetType, CrabSettings, DuckSettings, FishSettings};
+use tui::input;
+use tui::pad;
+use tui::surface::Surface;
+
+#[derive(PartialEq)]
+pub enum MenuAction {
+    Stay,
+    Close,
+    Save,
+}
+
+#[derive(PartialEq)]
+enum MenuField {
+    AssetType,
+    Buoyancy,
+    Path,
+}
+
+pub struct SaveMenu {
+    sort: AssetType,
+    path: String,
+    field: MenuField,
+}
+
+impl SaveMenu {
+    pub fn new(sort: AssetType, path: &str) -> SaveMenu {
+        return SaveMenu {
+            sort,
+            path: String::from(path),
+            field: MenuField::AssetType,
+        };
+    }
+
+    pub fn get_asset_type(&self) -> &AssetType {
+        return &self.sort;
+    }
+
+    pub fn get_path(&self) -> &str {
+        return &self.path;
+    }
+
+    pub fn print(&self, surface: &mut dyn Surface) {
+        match &self.sort {
+            AssetType::Fish(_settings) => pad::print_line(
+                surface,
+                &self.field_line(MenuField::AssetType, "Asset Type: < Fish > "),
+            ),
+            AssetType::Duck(settings) => {
+                pad::print_line(
+                    surface,
+                    &self.field_line(MenuField::AssetType, "Asset Type: < Duck > "),
+                );
+                pad::print_line(
+                    surface,
+                    &self.field_line(
+                        MenuField::Buoyancy,
+                        &format!("Buoyancy: [{}]", settings.buoyancy),
+                    ),
+                );
+            }
+            AssetType::Crab(_settings) => pad::print_line(
+                surface,
+                &self.field_line(MenuField::AssetType, "Asset Type: < Crab > "),
+            ),
+        }
+        pad::print_line(
+            surface,
+            &self.field_line(MenuField::Path, &format!("Path: {}", self.path)),
+        );
+        pad::print_line(surface, "\r");
+        pad::print_line(surface, "\r[enter] save  [esc] back");
+    }
+
+    fn field_line(&self, field: MenuField, text: &str) -> String {
+        if self.field == field {
+            return format!("\r> {}", text);
+        }
+        return format!("\r  {}", text);
+    }
+
+    pub fn handle_input(&mut self, source: &mut dyn input::InputSource) -> MenuAction {
+        if let Some(press) = source.next_press() {
+            if press.key == input::Key::Esc {
+                return MenuAction::Close;
+            }
+            if press.key == input::Key::Enter {
+                if self.path.is_empty() {
+                    return MenuAction::Stay;
+                }
+                return MenuAction::Save;
+            }
+            if let input::Key::Direction(dir) = press.key {
+                match dir {
+                    input::Direction::Up => self.cycle_field(-1),
+                    input::Direction::Down => self.cycle_field(1),
+                    input::Direction::Left => {
+                        if self.field == MenuField::AssetType {
+                            self.cycle_asset_type(-1);
+                        }
+                    }
+                    input::Direction::Right => {
+                        if self.field == MenuField::AssetType {
+                            self.cycle_asset_type(1);
+                        }
+                    }
+                }
+            }
+            if self.field == MenuField::Path {
+                if let input::Key::Glyph(glyph) = press.key {
+                    self.path.push(glyph);
+                } else if press.key == input::Key::Backspace {
+                    self.path.pop();
+                }
+            }
+            if self.field == MenuField::Buoyancy {
+                if let AssetType::Duck(ref mut settings) = self.sort {
+                    if let input::Key::Glyph(glyph) = press.key {
+                        if let Some(num) = glyph.to_digit(10) {
+                            settings.buoyancy = num as usize;
+                        }
+                    }
+                }
+            }
+            return MenuAction::Stay;
+        }
+        return MenuAction::Stay;
+    }
+
+    fn cycle_field(&mut self, delta: isize) {
+        let has_buoyancy = matches!(self.sort, AssetType::Duck(..));
+        // there are only ever two or three fields so just walk them
+        self.field = match (&self.field, delta > 0) {
+            (MenuField::AssetType, true) if has_buoyancy => MenuField::Buoyancy,
+            (MenuField::AssetType, true) => MenuField::Path,
+            (MenuField::Buoyancy, true) => MenuField::Path,
+            (MenuField::Path, true) => MenuField::AssetType,
+            (MenuField::AssetType, false) => MenuField::Path,
+            (MenuField::Buoyancy, false) => MenuField::AssetType,
+            (MenuField::Path, false) if has_buoyancy => MenuField::Buoyancy,
+            (MenuField::Path, false) => MenuField::AssetType,
+        };
+    }
+
+    fn cycle_asset_type(&mut self, delta: isize) {
+        // just use a match
+        if delta > 0 {
+            for _i in 0..delta {
+                match self.sort {
+                    AssetType::Fish(..) => {
+                        let new_type = AssetType::Duck(DuckSettings { buoyancy: 0 });
+                        self.sort = new_type;
+                    }
+                    AssetType::Duck(..) => {
+                        let new_type = AssetType::Crab(CrabSettings {});
+                        self.sort = new_type;
+                    }
+                    AssetType::Crab(..) => {
+                        let new_type = AssetType::Fish(FishSettings {});
+                        self.sort = new_type;
+                    }
+                }
+            }
+        } else {
+            for _i in 0..-delta {
+                match self.sort {
+                    AssetType::Fish(..) => {
+                        let new_type = AssetType::Crab(CrabSettings {});
+                        self.sort = new_type;
+                    }
+                    AssetType::Duck(..) => {
+                        let new_type = AssetType::Fish(FishSettings {});
+                        self.sort = new_type;
+                    }
+                    AssetType::Crab(..) => {
+                        let new_type = AssetType::Duck(DuckSettings { buoyancy: 0 });
+                        self.sort = new_type;
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,16 @@
+// the byofish editor, drawing and reading keys in a terminal on top of the
+// asset model
+
+pub mod canvas;
+pub mod decorations;
+pub mod editor;
+pub mod input;
+pub mod keymap;
+pub mod layout;
+pub mod menu;
+pub mod mode;
+pub mod onion_skin;
+pub mod pad;
+pub mod playback;
+pub mod surface;
+pub mod terminal;
@@ -0,0 +1,9 @@
+#[derive(PartialEq)]
+pub enum EditorMode {
+    Glyph,
+    // like glyph but the cursor moves along as you type
+    Text,
+    Color,
+    Highlight,
+    Save,
+}
@@ -0,0 +1,60 @@
+use animation::{Animation, Position};
+
+pub struct OnionSkin {
+    enabled: bool,
+    // how many frames before and after the current one are shown
+    back: usize,
+    forward: usize,
+}
+
+impl OnionSkin {
+    pub fn new(back: usize, forward: usize) -> OnionSkin {
+        return OnionSkin {
+            enabled: false,
+            back,
+            forward,
+        };
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        return self.enabled;
+    }
+
+    pub fn toggle(&mut self) {
+        self.enabled = !self.enabled;
+    }
+
+    // the glyph of the closest shown frame that has something at the position,
+    // the animation loops so the frame before the first is the last
+    pub fn ghost(
+        &self,
+        animation: &Animation,
+        frame_idx: usize,
+        position: Position,
+    ) -> Option<char> {
+        if !self.enabled {
+            return None;
+        }
+        let frame_num = animation.len() as isize;
+        for distance in 1..=self.back.max(self.forward) {
+            let mut offsets: Vec<isize> = Vec::new();
+            if distance <= self.back {
+                offsets.push(-(distance as isize));
+            }
+            if distance <= self.forward {
+                offsets.push(distance as isize);
+            }
+            for offset in offsets {
+                let other_idx = (frame_idx as isize + offset).rem_euclid(frame_num) as usize;
+                if other_idx == frame_idx {
+                    continue;
+                }
+                let glyph = animation[other_idx][position.y][position.x].glyph;
+                if glyph != ' ' {
+                    return Some(glyph);
+                }
+            }
+        }
+        return None;
+    }
+}
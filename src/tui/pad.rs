@@ -0,0 +1,43 @@
+use tui::surface::Surface;
+use tui::terminal;
+
+// I <3 tuples
+// ===========
+// 0 width
+// 1 height
+
+pub fn print_line(surface: &mut dyn Surface, string: &str) {
+    // right pad (^:
+    surface.set_background_color(terminal::Color::Default);
+    surface.print(string);
+    new_line(surface);
+}
+
+pub fn new_line(surface: &mut dyn Surface) {
+    surface.set_background_color(terminal::Color::Default);
+    let size = surface.size();
+    let position = surface.cursor();
+    let padding_len = size.0.saturating_sub(position.0);
+    surface.print(&format!("{}\n\r", " ".repeat(padding_len)));
+}
+
+pub fn to_end(surface: &mut dyn Surface) {
+    surface.set_background_color(terminal::Color::Default);
+    let size = surface.size();
+    let position = surface.cursor();
+    let padding_len = size.0.saturating_sub(position.0);
+    surface.print(&format!("{}\n\r", " ".repeat(padding_len)));
+
+    // nothing left to pad when the screen is already full
+    for _i in 0..size.1.saturating_sub(position.1 + 2) {
+        surface.print(&format!("{}\n\r", " ".repeat(size.0)));
+        /*
+        print!(
+            "height:{} - {} = {}\n\r",
+            size.1,
+            position.1 + 1,
+            size.1 - (position.1 + 1)
+        );
+        */
+    }
+}
@@ -0,0 +1,138 @@
+use asset::Asset;
+use std::time::{Duration, Instant};
+
+pub struct Playback {
+    playing: bool,
+    // None plays until paused
+    loops: Option<usize>,
+    loops_remaining: Option<usize>,
+    // a loop is finished each time playback comes back around to this frame
+    start_frame: usize,
+    last_tick: Instant,
+}
+
+impl Playback {
+    pub fn new(loops: Option<usize>) -> Playback {
+        return Playback {
+            playing: false,
+            loops,
+            loops_remaining: loops,
+            start_frame: 0,
+            last_tick: Instant::now(),
+        };
+    }
+
+    pub fn is_playing(&self) -> bool {
+        return self.playing;
+    }
+
+    pub fn toggle(&mut self, asset: &Asset) {
+        if self.playing {
+            self.playing = false;
+            return;
+        }
+        self.playing = true;
+        self.loops_remaining = self.loops;
+        self.start_frame = asset.get_frame_idx();
+        self.last_tick = Instant::now();
+    }
+
+    // steps the asset to the next frame once the current one has been shown
+    // long enough, true when it did
+    pub fn update(&mut self, asset: &mut Asset) -> bool {
+        return self.update_at(asset, Instant::now());
+    }
+
+    fn update_at(&mut self, asset: &mut Asset, now: Instant) -> bool {
+        let frame_duration = Duration::from_millis(asset.get_frame_duration());
+        if !self.playing || now.duration_since(self.last_tick) < frame_duration {
+            return false;
+        }
+        self.last_tick = now;
+        // frames can be deleted while playing, the loop has to end on one
+        // that is still there
+        if self.start_frame >= asset.get_frame_num() {
+            self.start_frame = asset.get_frame_num() - 1;
+        }
+        asset.cycle_frame(1);
+        if asset.get_frame_idx() != self.start_frame {
+            return true;
+        }
+        if let Some(remaining) = self.loops_remaining {
+            if remaining <= 1 {
+                self.playing = false;
+            } else {
+                self.loops_remaining = Some(remaining - 1);
+            }
+        }
+        return true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use command::Command;
+    use serde_json::json;
+
+    fn three_frames() -> Asset {
+        let json = json!({
+            "forward_animation": {
+                "symbols": [["a"], ["b"], ["c"]],
+                "colors": [[" "], [" "], [" "]],
+                "highlights": [[" "], [" "], [" "]],
+                "durations": [100, null, 300],
+            },
+        });
+        let mut asset = Asset::from_json(&json).unwrap();
+        asset.set_default_duration(200);
+        return asset;
+    }
+
+    // plays until it stops and returns the frames shown after the first
+    fn play(playback: &mut Playback, asset: &mut Asset) -> Vec<usize> {
+        let mut shown = Vec::new();
+        let mut now = playback.last_tick;
+        while playback.is_playing() && shown.len() < 100 {
+            now += Duration::from_millis(asset.get_frame_duration());
+            assert!(playback.update_at(asset, now));
+            shown.push(asset.get_frame_idx());
+        }
+        return shown;
+    }
+
+    #[test]
+    fn waits_for_each_frames_own_duration() {
+        let mut asset = three_frames();
+        let mut playback = Playback::new(None);
+        playback.toggle(&asset);
+        let start = playback.last_tick;
+        let expected = [(99, 0), (100, 1), (299, 1), (300, 2), (599, 2), (600, 0)];
+        for (ms, frame_idx) in expected.iter() {
+            playback.update_at(&mut asset, start + Duration::from_millis(*ms));
+            assert_eq!(asset.get_frame_idx(), *frame_idx);
+        }
+    }
+
+    #[test]
+    fn stops_after_the_given_number_of_loops() {
+        let mut asset = three_frames();
+        asset.cycle_frame(1);
+        let mut playback = Playback::new(Some(2));
+        playback.toggle(&asset);
+        assert_eq!(play(&mut playback, &mut asset), vec![2, 0, 1, 2, 0, 1]);
+        assert!(!playback.is_playing());
+    }
+
+    #[test]
+    fn stops_when_the_start_frame_is_deleted() {
+        let mut asset = three_frames();
+        asset.cycle_frame(2);
+        let mut playback = Playback::new(Some(1));
+        playback.toggle(&asset);
+        asset.handle_command(&Command::DeleteFrame);
+        let shown = play(&mut playback, &mut asset);
+        assert!(!playback.is_playing());
+        assert_eq!(shown.last(), Some(&1));
+    }
+}
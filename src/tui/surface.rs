@@ -0,0 +1,245 @@
+extern crate crossterm;
+use self::crossterm::QueueableCommand;
+use std::io::Write;
+use tui::terminal::{to_crossterm_color, Color};
+
+// somewhere the editor can draw, text is written at the cursor like on a
+// terminal in raw mode, "\r" goes back to the start of the line and "\n"
+// goes down a line without going back
+pub trait Surface {
+    // width then height in cells
+    fn size(&self) -> (usize, usize);
+    // column then row the next glyph is written at
+    fn cursor(&self) -> (usize, usize);
+    fn move_to(&mut self, x: usize, y: usize);
+    fn set_foreground_color(&mut self, color: Color);
+    fn set_background_color(&mut self, color: Color);
+    fn print(&mut self, text: &str);
+    fn flush(&mut self);
+    // blanks every cell and goes back to the top left, nothing is shown
+    // until the next flush
+    fn clear(&mut self);
+    // clears everything for a screen of the new width and height
+    fn resize(&mut self, width: usize, height: usize);
+}
+
+// draws into a back buffer and only sends the terminal the cells that
+// changed since the last flush
+pub struct TerminalSurface {
+    stdout: std::io::Stdout,
+    back: GridSurface,
+    // what the terminal is showing, None when it has to be redrawn in full
+    front: Option<Vec<Vec<Cell>>>,
+}
+
+impl TerminalSurface {
+    // no Default since this asks the terminal for its size
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> TerminalSurface {
+        let (width, height) = terminal_size();
+        return TerminalSurface {
+            stdout: std::io::stdout(),
+            back: GridSurface::new(width, height),
+            front: None,
+        };
+    }
+}
+
+fn terminal_size() -> (usize, usize) {
+    match crossterm::terminal::size() {
+        Ok((width, height)) => return (width as usize, height as usize),
+        Err(_) => return (0, 0),
+    }
+}
+
+impl Surface for TerminalSurface {
+    fn size(&self) -> (usize, usize) {
+        return self.back.size();
+    }
+
+    fn cursor(&self) -> (usize, usize) {
+        return self.back.cursor();
+    }
+
+    fn move_to(&mut self, x: usize, y: usize) {
+        self.back.move_to(x, y);
+    }
+
+    fn set_foreground_color(&mut self, color: Color) {
+        self.back.set_foreground_color(color);
+    }
+
+    fn set_background_color(&mut self, color: Color) {
+        self.back.set_background_color(color);
+    }
+
+    fn print(&mut self, text: &str) {
+        self.back.print(text);
+    }
+
+    // only the back buffer is cleared, so cells drawn the same again are
+    // still left out of the next flush
+    fn clear(&mut self) {
+        self.back.clear();
+    }
+
+    fn flush(&mut self) {
+        if self.front.is_none() {
+            self.stdout
+                .queue(crossterm::style::SetBackgroundColor(to_crossterm_color(
+                    Color::Default,
+                )))
+                .unwrap()
+                .queue(crossterm::terminal::Clear(
+                    crossterm::terminal::ClearType::All,
+                ))
+                .unwrap();
+        }
+        // the terminal keeps its colors and cursor between cells, so only
+        // send them when they change
+        let mut colors: Option<(Color, Color)> = None;
+        let mut terminal_cursor: Option<(usize, usize)> = None;
+        for (y, row) in self.back.cells.iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                if let Some(front) = &self.front {
+                    if front[y][x] == *cell {
+                        continue;
+                    }
+                }
+                if terminal_cursor != Some((x, y)) {
+                    self.stdout
+                        .queue(crossterm::cursor::MoveTo(x as u16, y as u16))
+                        .unwrap();
+                }
+                if colors != Some((cell.foreground_color, cell.background_color)) {
+                    self.stdout
+                        .queue(crossterm::style::SetForegroundColor(to_crossterm_color(
+                            cell.foreground_color,
+                        )))
+                        .unwrap()
+                        .queue(crossterm::style::SetBackgroundColor(to_crossterm_color(
+                            cell.background_color,
+                        )))
+                        .unwrap();
+                    colors = Some((cell.foreground_color, cell.background_color));
+                }
+                self.stdout
+                    .queue(crossterm::style::Print(cell.glyph))
+                    .unwrap();
+                terminal_cursor = Some((x + 1, y));
+            }
+        }
+        self.stdout.flush().unwrap();
+        self.front = Some(self.back.cells.clone());
+    }
+
+    // starts over at the new size, everything gets sent again on the next flush
+    fn resize(&mut self, width: usize, height: usize) {
+        self.back = GridSurface::new(width, height);
+        self.front = None;
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Cell {
+    pub glyph: char,
+    pub foreground_color: Color,
+    pub background_color: Color,
+}
+
+pub const BLANK_CELL: Cell = Cell {
+    glyph: ' ',
+    foreground_color: Color::Default,
+    background_color: Color::Default,
+};
+
+// keeps what was drawn in memory, anything past the edges is dropped
+pub struct GridSurface {
+    cells: Vec<Vec<Cell>>,
+    cursor: (usize, usize),
+    foreground_color: Color,
+    background_color: Color,
+}
+
+impl GridSurface {
+    pub fn new(width: usize, height: usize) -> GridSurface {
+        return GridSurface {
+            cells: vec![vec![BLANK_CELL; width]; height],
+            cursor: (0, 0),
+            foreground_color: Color::Default,
+            background_color: Color::Default,
+        };
+    }
+
+    pub fn get_cell(&self, x: usize, y: usize) -> Cell {
+        return self.cells[y][x];
+    }
+
+    // the glyphs of one row with the trailing blanks left off
+    pub fn row_text(&self, y: usize) -> String {
+        let row: String = self.cells[y].iter().map(|cell| cell.glyph).collect();
+        return String::from(row.trim_end());
+    }
+
+    // every row, one per line
+    pub fn text(&self) -> String {
+        let rows: Vec<String> = (0..self.cells.len()).map(|y| self.row_text(y)).collect();
+        return rows.join("\n");
+    }
+}
+
+impl Surface for GridSurface {
+    fn size(&self) -> (usize, usize) {
+        match self.cells.first() {
+            Some(row) => return (row.len(), self.cells.len()),
+            None => return (0, 0),
+        }
+    }
+
+    fn cursor(&self) -> (usize, usize) {
+        return self.cursor;
+    }
+
+    fn move_to(&mut self, x: usize, y: usize) {
+        self.cursor = (x, y);
+    }
+
+    fn set_foreground_color(&mut self, color: Color) {
+        self.foreground_color = color;
+    }
+
+    fn set_background_color(&mut self, color: Color) {
+        self.background_color = color;
+    }
+
+    fn print(&mut self, text: &str) {
+        for glyph in text.chars() {
+            let (x, y) = self.cursor;
+            match glyph {
+                '\r' => self.cursor.0 = 0,
+                '\n' => self.cursor.1 += 1,
+                _ => {
+                    if y < self.cells.len() && x < self.cells[y].len() {
+                        self.cells[y][x] = Cell {
+                            glyph,
+                            foreground_color: self.foreground_color,
+                            background_color: self.background_color,
+                        };
+                    }
+                    self.cursor.0 += 1;
+                }
+            }
+        }
+    }
+
+    fn flush(&mut self) {}
+
+    fn clear(&mut self) {
+        let (width, height) = self.size();
+        *self = GridSurface::new(width, height);
+    }
+
+    fn resize(&mut self, width: usize, height: usize) {
+        *self = GridSurface::new(width, height);
+    }
+}
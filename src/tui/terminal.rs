@@ -1,29 +1,19 @@
 extern crate crossterm;
 use self::crossterm::ExecutableCommand;
+use std::sync::atomic::{AtomicBool, Ordering};
 
-#[derive(Clone, Copy, PartialEq, Debug)]
-pub enum Color {
-    Black,
-    DarkGrey,
-    Red,
-    Green,
-    Yellow,
-    Blue,
-    Magenta,
-    Cyan,
-    White,
-    DarkRed,
-    DarkGreen,
-    DarkYellow,
-    DarkBlue,
-    DarkMagenta,
-    DarkCyan,
-    Grey,
-    Default,
-}
+pub use color_glyph::Color;
+
+// set while the terminal is in raw mode so it's only put back once
+static ACTIVE: AtomicBool = AtomicBool::new(false);
 
 pub fn init() {
+    ACTIVE.store(true, Ordering::SeqCst);
     crossterm::terminal::enable_raw_mode().unwrap();
+    // the user's scrollback is left alone and comes back on reset
+    std::io::stdout()
+        .execute(crossterm::terminal::EnterAlternateScreen)
+        .unwrap();
     std::io::stdout().execute(crossterm::cursor::Hide).unwrap();
     std::io::stdout()
         .execute(crossterm::terminal::DisableLineWrap)
@@ -35,18 +25,44 @@ pub fn init() {
         .unwrap();
 }
 
+// safe to call at any point, even when init hasn't been or something has
+// already gone wrong, so errors are ignored rather than unwrapped
 pub fn reset() {
-    std::io::stdout()
-        .execute(crossterm::terminal::EnableLineWrap)
-        .unwrap();
-    std::io::stdout().execute(crossterm::cursor::Show).unwrap();
-    crossterm::terminal::disable_raw_mode().unwrap();
+    if !ACTIVE.swap(false, Ordering::SeqCst) {
+        return;
+    }
+    let mut stdout = std::io::stdout();
+    let _ = stdout.execute(crossterm::style::ResetColor);
+    let _ = stdout.execute(crossterm::terminal::LeaveAlternateScreen);
+    let _ = stdout.execute(crossterm::terminal::EnableLineWrap);
+    let _ = stdout.execute(crossterm::cursor::Show);
+    let _ = crossterm::terminal::disable_raw_mode();
 }
 
-pub fn home_cursor() {
-    std::io::stdout()
-        .execute(crossterm::cursor::MoveTo(0, 0))
-        .unwrap();
+// sets the terminal up for the editor and puts it back when dropped, which
+// also happens while unwinding from a panic
+pub struct TerminalGuard {}
+
+impl TerminalGuard {
+    // no Default since this takes over the terminal and the panic hook
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> TerminalGuard {
+        // the default hook prints the panic message, which would be mangled
+        // by raw mode or lost on the alternate screen, so reset first
+        let default_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            reset();
+            default_hook(info);
+        }));
+        init();
+        return TerminalGuard {};
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        reset();
+    }
 }
 
 pub fn set_foreground_color(color: Color) {
@@ -65,7 +81,7 @@ pub fn set_background_color(color: Color) {
         .unwrap();
 }
 
-fn to_crossterm_color(color: Color) -> crossterm::style::Color {
+pub fn to_crossterm_color(color: Color) -> crossterm::style::Color {
     match color {
         Color::Black => return crossterm::style::Color::Black,
         Color::DarkGrey => return crossterm::style::Color::DarkGrey,
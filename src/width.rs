@@ -0,0 +1,38 @@
+extern crate unicode_width;
+use self::unicode_width::UnicodeWidthChar;
+
+// every glyph in a frame takes up exactly one terminal cell, so anything
+// narrower or wider would knock the rest of the line out of place
+pub fn is_single_width(glyph: char) -> bool {
+    return glyph.width() == Some(1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_glyphs_one_cell_wide() {
+        for glyph in ['a', '<', '°', '≈', '─', '┏', '▲', 'é', 'ж'].iter() {
+            assert!(is_single_width(*glyph), "{:?}", glyph);
+        }
+    }
+
+    #[test]
+    fn rejects_wide_glyphs() {
+        for glyph in ['⚡', '☕', '✅', '🀄', '🐟', '漢', 'Ａ', '가'].iter() {
+            assert!(!is_single_width(*glyph), "{:?}", glyph);
+        }
+    }
+
+    #[test]
+    fn rejects_zero_width_glyphs() {
+        for glyph in [
+            '\u{0301}', '\u{0610}', '\u{0E31}', '\u{200B}', '\u{200D}', '\u{FE0F}', '\t', '\u{7F}',
+        ]
+        .iter()
+        {
+            assert!(!is_single_width(*glyph), "{:?}", glyph);
+        }
+    }
+}
@@ -0,0 +1,170 @@
+#![allow(clippy::needless_return)]
+
+extern crate byofish;
+#[macro_use]
+extern crate serde_json;
+
+use byofish::asset::Asset;
+use byofish::asset_type::AssetType;
+use byofish::color_glyph::Color;
+use byofish::command::Command;
+use byofish::direction::Direction;
+use byofish::tui::canvas::print_asset;
+use byofish::tui::decorations;
+use byofish::tui::editor::Editor;
+use byofish::tui::input::{Key, Modifier, Press, ScriptedInput};
+use byofish::tui::menu::SaveMenu;
+use byofish::tui::onion_skin::OnionSkin;
+use byofish::tui::playback::Playback;
+use byofish::tui::surface::{GridSurface, Surface};
+
+fn fish() -> Asset {
+    let json = json!({
+        "forward_animation": {
+            "symbols": [["<°)))><", "  ≈≈   "], ["<°)))><", "   ≈≈  "]],
+            "colors": [["rrrrrrr", "  bb   "], ["rrrrrrr", "   bb  "]],
+            "highlights": [["       ", "       "], ["       ", "       "]]
+        }
+    });
+    return Asset::from_json(&json).unwrap();
+}
+
+#[test]
+fn draws_the_frame_inside_a_border() {
+    let asset = fish();
+    let mut surface = GridSurface::new(12, 4);
+    print_asset(&mut surface, &asset, false, &OnionSkin::new(1, 0));
+    assert_eq!(surface.text(), "┏━━━━━━━┓\n┃<°)))><┃\n┃  ≈≈   ┃\n┗━━━━━━━┛");
+    let eye = surface.get_cell(2, 1);
+    assert_eq!(eye.glyph, '°');
+    assert_eq!(eye.foreground_color, Color::Red);
+    assert_eq!(surface.get_cell(3, 2).foreground_color, Color::Blue);
+}
+
+#[test]
+fn draws_the_cursor_where_it_is() {
+    let mut asset = fish();
+    asset.handle_command(&Command::MoveCursor(Direction::Down));
+    asset.handle_command(&Command::MoveCursor(Direction::Right));
+    let mut surface = GridSurface::new(12, 4);
+    print_asset(&mut surface, &asset, true, &OnionSkin::new(1, 0));
+    assert_eq!(surface.row_text(2), "┃ X≈≈   ┃");
+}
+
+#[test]
+fn draws_the_selection_and_onion_skin() {
+    let mut asset = fish();
+    asset.handle_command(&Command::CycleFrame(1));
+    asset.handle_command(&Command::ToggleSelection);
+    asset.handle_command(&Command::MoveCursor(Direction::Right));
+    let mut onion_skin = OnionSkin::new(1, 0);
+    onion_skin.toggle();
+    let mut surface = GridSurface::new(12, 4);
+    print_asset(&mut surface, &asset, false, &onion_skin);
+
+    assert_eq!(surface.get_cell(1, 1).background_color, Color::DarkGrey);
+    assert_eq!(surface.get_cell(3, 1).background_color, Color::Default);
+    // the previous frame shows through the empty cells
+    let ghost = surface.get_cell(3, 2);
+    assert_eq!(ghost.glyph, '≈');
+    assert_eq!(ghost.foreground_color, Color::DarkGrey);
+}
+
+#[test]
+fn draws_the_frame_indicator_and_brush() {
+    let mut asset = fish();
+    asset.handle_command(&Command::MarkFrame);
+    asset.handle_command(&Command::CycleFrame(1));
+    asset.handle_command(&Command::PickBrush);
+    let mut surface = GridSurface::new(40, 2);
+    decorations::print_frame_indicator(
+        &mut surface,
+        asset.get_frame_idx(),
+        asset.get_frame_num(),
+        asset.get_frame_mark(),
+    );
+    decorations::print_brush(&mut surface, &asset.get_brush());
+    assert_eq!(surface.text(), "0[m]1[*]\nbrush:[<] color:r highlight:-");
+    assert_eq!(surface.get_cell(7, 1).foreground_color, Color::Red);
+    assert_eq!(surface.cursor(), (0, 2));
+}
+
+#[test]
+fn redraws_the_editor_after_a_press() {
+    let save_menu = SaveMenu::new(AssetType::from_name("fish").unwrap(), "fish.json");
+    let mut editor = Editor::new(fish(), save_menu, Playback::new(None), OnionSkin::new(1, 0));
+    let mut surface = GridSurface::new(60, 14);
+    editor.draw(&mut surface);
+    assert_eq!(surface.row_text(1), "┏━━━━━━━┓");
+    assert_eq!(
+        surface.row_text(8),
+        "mode:glyph animation:forward fill:glyph duration:250ms"
+    );
+
+    editor.update(&mut ScriptedInput::new(vec![Press::new(Key::Tab, None)]));
+    assert!(editor.needs_draw());
+    editor.draw(&mut surface);
+    assert_eq!(
+        surface.row_text(8),
+        "mode:text animation:forward fill:glyph duration:250ms"
+    );
+}
+
+#[test]
+fn fits_the_editor_into_a_small_window() {
+    let save_menu = SaveMenu::new(AssetType::from_name("fish").unwrap(), "fish.json");
+    let mut asset = fish();
+    for _i in 0..5 {
+        asset.handle_command(&Command::DuplicateFrame);
+    }
+    for _i in 0..6 {
+        asset.handle_command(&Command::MoveCursor(Direction::Right));
+    }
+    let mut editor = Editor::new(asset, save_menu, Playback::new(None), OnionSkin::new(1, 0));
+    let mut surface = GridSurface::new(8, 7);
+    editor.draw(&mut surface);
+    // the frame bar keeps the current frame and the canvas follows the cursor
+    assert_eq!(surface.row_text(0), "<5[*]>");
+    assert_eq!(surface.row_text(2), "┃)))><┃");
+    // the color guide makes room for the canvas
+    assert_eq!(surface.row_text(4), "brush:[");
+
+    surface.resize(30, 12);
+    editor.draw(&mut surface);
+    assert_eq!(surface.row_text(0), "0[ ]1[ ]2[ ]3[ ]4[ ]5[*]6[ ]");
+    assert_eq!(surface.row_text(2), "┃<°)))><┃");
+    assert_eq!(surface.row_text(5), "argybmcw");
+}
+
+#[test]
+fn clears_what_a_bigger_canvas_left_behind() {
+    let save_menu = SaveMenu::new(AssetType::from_name("fish").unwrap(), "fish.json");
+    let json = json!({
+        "forward_animation": {
+            "symbols": [["ab    "]],
+            "colors": [["      "]],
+            "highlights": [["      "]]
+        },
+        "flipped_animation": {
+            "symbols": [["    ba"]],
+            "colors": [["      "]],
+            "highlights": [["      "]]
+        }
+    });
+    let asset = Asset::from_json(&json).unwrap();
+    let mut editor = Editor::new(asset, save_menu, Playback::new(None), OnionSkin::new(1, 0));
+    let mut surface = GridSurface::new(40, 10);
+    editor.draw(&mut surface);
+    assert_eq!(surface.row_text(1), "┏━━━━━━┓");
+
+    let alt = |glyph| Press::new(Key::Glyph(glyph), Some(Modifier::Alt));
+    editor.update(&mut ScriptedInput::new(vec![alt('t')]));
+    editor.draw(&mut surface);
+    editor.update(&mut ScriptedInput::new(vec![Press::new(Key::Enter, None)]));
+    editor.draw(&mut surface);
+    editor.update(&mut ScriptedInput::new(vec![Press::new(Key::Esc, None)]));
+    editor.draw(&mut surface);
+    assert_eq!(surface.row_text(1), "┏━━┓");
+    assert_eq!(surface.row_text(2), "┃ab┃");
+    assert_eq!(surface.row_text(3), "┗━━┛");
+}
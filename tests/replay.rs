@@ -0,0 +1,182 @@
+#![allow(clippy::needless_return)]
+
+extern crate byofish;
+#[macro_use]
+extern crate serde_json;
+
+use byofish::animation::Size;
+use byofish::asset::Asset;
+use byofish::asset_type::AssetType;
+use byofish::open_json::open_json;
+use byofish::tui::editor::Editor;
+use byofish::tui::input::{Press, ScriptedInput};
+use byofish::tui::menu::SaveMenu;
+use byofish::tui::mode::EditorMode;
+use byofish::tui::onion_skin::OnionSkin;
+use byofish::tui::playback::Playback;
+use std::path::{Path, PathBuf};
+
+fn temp_path(name: &str) -> PathBuf {
+    return std::env::temp_dir().join(format!("byofish_{}_{}", std::process::id(), name));
+}
+
+fn blank_editor(width: usize, height: usize, path: &Path) -> Editor {
+    let asset = Asset::new_blank(Size { width, height });
+    let save_menu = SaveMenu::new(
+        AssetType::from_name("fish").unwrap(),
+        &path.to_string_lossy(),
+    );
+    return Editor::new(asset, save_menu, Playback::new(None), OnionSkin::new(1, 0));
+}
+
+fn script(names: &[&str]) -> ScriptedInput {
+    let presses = names
+        .iter()
+        .map(|name| Press::from_name(name).unwrap())
+        .collect();
+    return ScriptedInput::new(presses);
+}
+
+// runs the whole script, false if the editor quit along the way
+fn replay(editor: &mut Editor, input: &mut ScriptedInput) -> bool {
+    while !input.is_empty() {
+        if !editor.update(input) {
+            return false;
+        }
+    }
+    return true;
+}
+
+#[test]
+fn draws_colors_and_saves_a_fish() {
+    let path = temp_path("fish.json");
+    let mut editor = blank_editor(3, 2, &path);
+    let mut input = script(&[
+        // type the body in text mode
+        "tab",
+        "<",
+        "o",
+        "<",
+        "down",
+        "home",
+        "~",
+        "space",
+        "~",
+        // color the eye red and undo a stray glyph
+        "tab",
+        "up",
+        "left",
+        "r",
+        "tab",
+        "tab",
+        "x",
+        "ctrl+z",
+        // add a second frame as a copy and shift the whole animation over
+        "alt+d",
+        "alt+right",
+        // save through the menu
+        "enter",
+        "enter",
+    ]);
+    assert!(replay(&mut editor, &mut input));
+    assert!(*editor.get_mode() == EditorMode::Glyph);
+    assert!(!editor.get_asset().is_dirty());
+
+    let saved = open_json(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let forward = json!({
+        "symbols": [["<<o", "~~ "], ["<<o", "~~ "]],
+        "colors": [["  r", "   "], ["  r", "   "]],
+        "highlights": [["   ", "   "], ["   ", "   "]]
+    });
+    assert_eq!(saved["forward_animation"], forward);
+}
+
+#[test]
+fn replays_a_script_file() {
+    let script_path = temp_path("script.txt");
+    std::fs::write(
+        &script_path,
+        "# draw a dash\n-\nright\nshift+right\n\nesc\n",
+    )
+    .unwrap();
+    let mut input = ScriptedInput::from_file(&script_path).unwrap();
+    std::fs::remove_file(&script_path).unwrap();
+
+    let mut editor = blank_editor(2, 1, &temp_path("unused.json"));
+    assert!(replay(&mut editor, &mut input));
+    assert_eq!(
+        editor.get_status(),
+        "unsaved changes, press esc again to quit"
+    );
+    let exported = editor.get_asset().export();
+    assert_eq!(exported["forward_animation"]["symbols"], json!([["-"]]));
+
+    let mut input = script(&["esc"]);
+    assert!(!replay(&mut editor, &mut input));
+}
+
+#[test]
+fn rejects_unknown_keys_in_scripts() {
+    let script_path = temp_path("bad_script.txt");
+    std::fs::write(&script_path, "a\nctrl+nope\n").unwrap();
+    let error = ScriptedInput::from_file(&script_path).err().unwrap();
+    std::fs::remove_file(&script_path).unwrap();
+    assert_eq!(error.to_string(), "line 2: ctrl+nope is not a key");
+}
+
+#[test]
+fn saving_keeps_what_the_editor_does_not_know_about() {
+    let path = temp_path("duck.json");
+    let loaded = json!({
+        "buoyancy": 3,
+        "credit": "someone",
+        "forward_animation": {
+            "symbols": [["ab"]],
+            "colors": [["  "]],
+            "highlights": [["  "]],
+            "note": "kept"
+        },
+        "flipped_animation": {
+            "symbols": [["ba"]],
+            "colors": [["  "]],
+            "highlights": [["  "]]
+        }
+    });
+    let asset = Asset::from_json(&loaded).unwrap();
+    let asset_type = AssetType::from_json(&loaded).unwrap().unwrap();
+    let save_menu = SaveMenu::new(asset_type, &path.to_string_lossy());
+    let mut editor = Editor::new(asset, save_menu, Playback::new(None), OnionSkin::new(1, 0));
+    let mut input = script(&["x", "enter", "enter"]);
+    assert!(replay(&mut editor, &mut input));
+
+    let saved = open_json(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(saved["buoyancy"], json!(3));
+    assert_eq!(saved["credit"], json!("someone"));
+    assert_eq!(saved["forward_animation"]["note"], json!("kept"));
+    assert_eq!(saved["forward_animation"]["symbols"], json!([["xb"]]));
+}
+
+#[test]
+fn saves_and_reloads_rows_ending_in_a_backslash() {
+    let path = temp_path("backslash.json");
+    let loaded = json!({
+        "forward_animation": {
+            "symbols": [["/o\\", "[,]", "\"\\\""]],
+            "colors": [["   ", "   ", "   "]],
+            "highlights": [["   ", "   ", "   "]]
+        }
+    });
+    let mut asset = Asset::from_json(&loaded).unwrap();
+    asset
+        .save(&path, &AssetType::from_name("fish").unwrap())
+        .unwrap();
+    let saved = open_json(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(
+        saved["forward_animation"]["symbols"],
+        loaded["forward_animation"]["symbols"]
+    );
+    assert!(Asset::from_json(&saved).is_ok());
+}